@@ -0,0 +1,1389 @@
+use crate::state::AppState;
+use crate::commands::response::Response;
+use crate::commands::bulk::{new_job_id, start_bulk_job, BulkProgress, BulkScanOptions, BULK_PROGRESS_EVENT};
+use crate::commands::keys::length_command;
+use crate::decoder::match_pattern;
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter, State};
+
+// 分析报告，按任务 ID 保存在 AppState 中，任务运行期间随每批结果更新
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum AnalysisReport {
+    BigKeys(BigKeyReport),
+    Prefixes(PrefixReport),
+    Ttl(TtlReport),
+    HotKeys(HotKeyReport),
+}
+
+// 任务与读取方共享的报告，任务直接在原处更新，不再每批复制整份报告
+pub type SharedReport = Arc<Mutex<AnalysisReport>>;
+
+// 登记任务的报告，返回共享引用供任务更新
+fn register_report(state: &State<'_, AppState>, job_id: &str, report: AnalysisReport) -> SharedReport {
+    let shared = Arc::new(Mutex::new(report));
+    state.reports.lock().unwrap().insert(job_id.to_string(), shared.clone());
+    shared
+}
+
+// 取出报告的共享引用，读取报告时不占用报告表的锁
+fn find_report(state: &State<'_, AppState>, job_id: &str) -> Option<SharedReport> {
+    state.reports.lock().unwrap().get(job_id).cloned()
+}
+
+impl AnalysisReport {
+    // 导出为 CSV，每行一条明细
+    fn to_csv(&self) -> String {
+        let (header, rows) = match self {
+            AnalysisReport::BigKeys(report) => report.csv_rows(),
+            AnalysisReport::Prefixes(report) => report.csv_rows(),
+            AnalysisReport::Ttl(report) => report.csv_rows(),
+            AnalysisReport::HotKeys(report) => report.csv_rows(),
+        };
+
+        let mut csv = header.join(",");
+        csv.push('\n');
+        for row in rows {
+            let fields: Vec<String> = row.iter().map(|field| csv_field(field)).collect();
+            csv.push_str(&fields.join(","));
+            csv.push('\n');
+        }
+        csv
+    }
+}
+
+fn csv_header(columns: &[&str]) -> Vec<String> {
+    columns.iter().map(|c| c.to_string()).collect()
+}
+
+// 包含逗号、引号或换行的字段用双引号包裹
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+// 大键排序依据
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RankBy {
+    #[default]
+    Bytes,
+    Length,
+}
+
+fn default_top_n() -> usize {
+    10
+}
+
+fn default_samples() -> usize {
+    5
+}
+
+// 大键扫描参数
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BigKeyOptions {
+    #[serde(default = "default_top_n")]
+    pub top_n: usize, // 每种类型保留的大键数量
+    #[serde(default)]
+    pub min_bytes: Option<u64>, // 内存占用达到该字节数视为大键
+    #[serde(default)]
+    pub min_length: Option<u64>, // 元素个数达到该值视为大键，与 min_bytes 满足其一即可
+    #[serde(default)]
+    pub rank_by: RankBy,
+    #[serde(default = "default_samples")]
+    pub samples: usize, // MEMORY USAGE 的 SAMPLES 参数，0 表示统计全部元素（较慢）
+}
+
+// 单个键的大小
+#[derive(Debug, Clone, Serialize)]
+pub struct BigKeyEntry {
+    pub key: String,
+    #[serde(rename = "type")]
+    pub key_type: String,
+    pub bytes: Option<u64>,  // MEMORY USAGE，服务端不支持时为空
+    pub length: Option<u64>, // 元素个数，字符串为字节长度
+}
+
+impl BigKeyEntry {
+    fn rank(&self, rank_by: RankBy) -> u64 {
+        match rank_by {
+            RankBy::Bytes => self.bytes.unwrap_or(0),
+            RankBy::Length => self.length.unwrap_or(0),
+        }
+    }
+}
+
+// 按类型汇总的统计
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct BigKeyTypeStats {
+    pub keys: usize,
+    pub total_bytes: u64,
+    pub total_length: u64,
+    pub big_keys: usize, // 达到阈值的键数
+    pub top: Vec<BigKeyEntry>,
+}
+
+// 大键扫描报告
+#[derive(Debug, Clone, Serialize)]
+pub struct BigKeyReport {
+    pub options: BigKeyOptions,
+    pub analyzed: usize,
+    pub types: BTreeMap<String, BigKeyTypeStats>,
+}
+
+impl BigKeyReport {
+    fn new(options: BigKeyOptions) -> Self {
+        BigKeyReport {
+            options,
+            analyzed: 0,
+            types: BTreeMap::new(),
+        }
+    }
+
+    fn is_big(&self, entry: &BigKeyEntry) -> bool {
+        match (self.options.min_bytes, self.options.min_length) {
+            (None, None) => true,
+            (min_bytes, min_length) => {
+                min_bytes.is_some_and(|min| entry.bytes.unwrap_or(0) >= min)
+                    || min_length.is_some_and(|min| entry.length.unwrap_or(0) >= min)
+            }
+        }
+    }
+
+    fn add(&mut self, entry: BigKeyEntry) {
+        self.analyzed += 1;
+        let is_big = self.is_big(&entry);
+        let rank_by = self.options.rank_by;
+        let top_n = self.options.top_n;
+
+        let stats = self.types.entry(entry.key_type.clone()).or_default();
+        stats.keys += 1;
+        stats.total_bytes += entry.bytes.unwrap_or(0);
+        stats.total_length += entry.length.unwrap_or(0);
+        if !is_big {
+            return;
+        }
+
+        stats.big_keys += 1;
+        let rank = entry.rank(rank_by);
+        if stats.top.len() < top_n || stats.top.last().is_some_and(|last| last.rank(rank_by) < rank) {
+            let position = stats.top.partition_point(|e| e.rank(rank_by) >= rank);
+            stats.top.insert(position, entry);
+            stats.top.truncate(top_n);
+        }
+    }
+
+    fn csv_rows(&self) -> (Vec<String>, Vec<Vec<String>>) {
+        let rows = self
+            .types
+            .values()
+            .flat_map(|stats| stats.top.iter())
+            .map(|entry| {
+                vec![
+                    entry.key_type.clone(),
+                    entry.key.clone(),
+                    entry.bytes.map(|b| b.to_string()).unwrap_or_default(),
+                    entry.length.map(|l| l.to_string()).unwrap_or_default(),
+                ]
+            })
+            .collect();
+        (csv_header(&["type", "key", "bytes", "length"]), rows)
+    }
+}
+
+// 批量读取键的内存占用，单独使用一个 pipeline：MEMORY USAGE 被禁用或服务端不支持时
+// 整个 pipeline 都会失败，此时内存占用全部为空，不影响其他信息的读取
+fn read_memory_usage(conn: &mut redis::Connection, keys: &[String], samples: usize) -> Vec<Option<u64>> {
+    let mut pipe = redis::pipe();
+    for key in keys {
+        pipe.cmd("MEMORY").arg("USAGE").arg(key).arg("SAMPLES").arg(samples);
+    }
+    match pipe.query::<Vec<Option<u64>>>(conn) {
+        Ok(bytes) => bytes,
+        Err(_) => vec![None; keys.len()],
+    }
+}
+
+// 批量读取一批键的类型、内存占用和元素个数，扫描期间被删除的键会被跳过
+fn collect_big_keys(
+    conn: &mut redis::Connection,
+    keys: &[String],
+    samples: usize,
+) -> Result<Vec<BigKeyEntry>, String> {
+    let mut pipe = redis::pipe();
+    for key in keys {
+        pipe.cmd("TYPE").arg(key);
+    }
+    let types: Vec<String> = pipe.query(conn).map_err(|e| format!("获取键类型失败: {}", e))?;
+
+    let mut bytes = read_memory_usage(conn, keys, samples).into_iter();
+
+    let mut pipe = redis::pipe();
+    for (key, key_type) in keys.iter().zip(&types) {
+        if let Some(command) = length_command(key_type) {
+            pipe.cmd(command).arg(key);
+        }
+    }
+    let values: Vec<redis::Value> = pipe.query(conn).map_err(|e| format!("获取键大小失败: {}", e))?;
+
+    let mut values = values.into_iter();
+    let mut next_number = || {
+        values
+            .next()
+            .and_then(|v| redis::from_redis_value::<Option<u64>>(&v).ok().flatten())
+    };
+
+    let mut entries = Vec::new();
+    for (key, key_type) in keys.iter().zip(types) {
+        let bytes = bytes.next().flatten();
+        let length = length_command(&key_type).and_then(|_| next_number());
+        if key_type == "none" {
+            continue;
+        }
+        entries.push(BigKeyEntry {
+            key: key.clone(),
+            key_type,
+            bytes,
+            length,
+        });
+    }
+    Ok(entries)
+}
+
+// 启动大键扫描任务，返回任务 ID；进度通过批量任务事件推送，报告通过 get_analysis_report 获取
+#[tauri::command]
+pub async fn start_big_key_scan(
+    app: AppHandle,
+    connection_id: String,
+    options: BulkScanOptions,
+    big_key_options: BigKeyOptions,
+    state: State<'_, AppState>,
+) -> Result<Response<String>, String> {
+    let job_id = new_job_id();
+    let samples = big_key_options.samples;
+    let report = register_report(&state, &job_id, AnalysisReport::BigKeys(BigKeyReport::new(big_key_options)));
+
+    let response = start_bulk_job(app, &state, &connection_id, job_id.clone(), options, move |conn, keys| {
+        let entries = collect_big_keys(conn, keys, samples)?;
+        let analyzed = entries.len();
+        if let AnalysisReport::BigKeys(report) = &mut *report.lock().unwrap() {
+            for entry in entries {
+                report.add(entry);
+            }
+        }
+        Ok(analyzed)
+    })?;
+
+    if !response.success {
+        state.reports.lock().unwrap().remove(&job_id);
+    }
+    Ok(response)
+}
+
+// 按比例抽样，sample_rate 为 1 时保留全部键；按计数均匀选取，结果可复现
+struct Sampler {
+    rate: f64,
+    seen: u64,
+}
+
+impl Sampler {
+    fn new(rate: f64) -> Self {
+        Sampler { rate: rate.clamp(0.0, 1.0), seen: 0 }
+    }
+
+    fn take(&mut self) -> bool {
+        let before = (self.seen as f64 * self.rate).floor();
+        self.seen += 1;
+        (self.seen as f64 * self.rate).floor() > before
+    }
+}
+
+// 抽样键的类型、内存占用和剩余过期毫秒数
+struct KeySample {
+    key: String,
+    key_type: String,
+    bytes: u64,
+    pttl: i64,
+}
+
+// 对一批键抽样后用 pipeline 读取 TYPE / PTTL 和 MEMORY USAGE，扫描期间被删除的键会被跳过
+fn collect_samples(
+    conn: &mut redis::Connection,
+    keys: &[String],
+    sampler: &mut Sampler,
+    samples: usize,
+) -> Result<Vec<KeySample>, String> {
+    let keys: Vec<&String> = keys.iter().filter(|_| sampler.take()).collect();
+    if keys.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let mut pipe = redis::pipe();
+    for key in &keys {
+        pipe.cmd("TYPE").arg(*key);
+        pipe.cmd("PTTL").arg(*key);
+    }
+    let values: Vec<redis::Value> = pipe.query(conn).map_err(|e| format!("读取键信息失败: {}", e))?;
+    let keys: Vec<String> = keys.into_iter().cloned().collect();
+    let bytes = read_memory_usage(conn, &keys, samples);
+
+    let mut result = Vec::new();
+    for ((key, chunk), bytes) in keys.into_iter().zip(values.chunks(2)).zip(bytes) {
+        let key_type: String = redis::from_redis_value(&chunk[0]).unwrap_or_default();
+        let pttl: i64 = redis::from_redis_value(&chunk[1]).unwrap_or(-2);
+        if key_type == "none" || pttl == -2 {
+            continue;
+        }
+        result.push(KeySample {
+            key,
+            key_type,
+            bytes: bytes.unwrap_or(0),
+            pttl,
+        });
+    }
+    Ok(result)
+}
+
+// 前缀数超出上限后汇总剩余键的名称
+const OTHER_PREFIX: &str = "(other)";
+
+// 按分隔符取键名的前缀，最多 depth 层；键名本身不算前缀，例如 "a:b:c" 在 depth 为 3 时返回 "a" 和 "a:b"
+fn key_prefixes(key: &str, separator: &str, depth: usize) -> Vec<String> {
+    if separator.is_empty() {
+        return Vec::new();
+    }
+    key.match_indices(separator)
+        .take(depth)
+        .map(|(index, _)| key[..index].to_string())
+        .collect()
+}
+
+fn default_separator() -> String {
+    ":".to_string()
+}
+
+fn default_depth() -> usize {
+    3
+}
+
+fn default_sample_rate() -> f64 {
+    1.0
+}
+
+fn default_max_prefixes() -> usize {
+    10_000
+}
+
+// 前缀分析参数
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PrefixOptions {
+    #[serde(default = "default_separator")]
+    pub separator: String, // 连接配置中的键名分隔符
+    #[serde(default = "default_depth")]
+    pub depth: usize, // 最多统计的前缀层数
+    #[serde(default = "default_sample_rate")]
+    pub sample_rate: f64, // 抽样比例 (0, 1]，估算值按比例放大
+    #[serde(default = "default_samples")]
+    pub samples: usize, // MEMORY USAGE 的 SAMPLES 参数
+    #[serde(default = "default_max_prefixes")]
+    pub max_prefixes: usize, // 最多统计的前缀数，超出后新出现的前缀计入 other
+}
+
+// 单个前缀的统计（基于抽样的键）
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct PrefixStats {
+    pub prefix: String,
+    pub level: usize, // 前缀层级，根节点为 0
+    pub keys: usize,
+    pub total_bytes: u64,
+    pub avg_bytes: u64,
+    pub estimated_keys: u64, // 按抽样比例估算的键数
+    pub estimated_bytes: u64,
+    pub types: BTreeMap<String, usize>,
+    pub no_ttl: usize,      // 没有过期时间的键数
+    pub no_ttl_percent: f64,
+}
+
+impl PrefixStats {
+    fn new(prefix: String, level: usize) -> Self {
+        PrefixStats {
+            prefix,
+            level,
+            ..Default::default()
+        }
+    }
+
+    fn add(&mut self, sample: &KeySample, sample_rate: f64) {
+        self.keys += 1;
+        self.total_bytes += sample.bytes;
+        *self.types.entry(sample.key_type.clone()).or_default() += 1;
+        if sample.pttl == -1 {
+            self.no_ttl += 1;
+        }
+
+        self.avg_bytes = self.total_bytes / self.keys as u64;
+        self.no_ttl_percent = self.no_ttl as f64 * 100.0 / self.keys as f64;
+        self.estimated_keys = (self.keys as f64 / sample_rate).round() as u64;
+        self.estimated_bytes = (self.total_bytes as f64 / sample_rate).round() as u64;
+    }
+}
+
+// 前缀内存分析报告
+#[derive(Debug, Clone, Serialize)]
+pub struct PrefixReport {
+    pub options: PrefixOptions,
+    pub root: PrefixStats, // 全部抽样键的汇总
+    pub prefixes: BTreeMap<String, PrefixStats>,
+    pub other: PrefixStats, // 前缀数达到 max_prefixes 后，无法单独统计的键
+}
+
+impl PrefixReport {
+    fn new(options: PrefixOptions) -> Self {
+        PrefixReport {
+            options,
+            root: PrefixStats::new(String::new(), 0),
+            prefixes: BTreeMap::new(),
+            other: PrefixStats::new(OTHER_PREFIX.to_string(), 1),
+        }
+    }
+
+    // 前缀数达到上限后不再新增前缀，键在已有前缀中照常统计，缺少的层级整体计入 other
+    // 子前缀总是在父前缀之后创建，缺少某一层时更深的层级也不存在
+    fn add(&mut self, sample: &KeySample) {
+        let rate = self.options.sample_rate;
+        self.root.add(sample, rate);
+        for (level, prefix) in key_prefixes(&sample.key, &self.options.separator, self.options.depth)
+            .into_iter()
+            .enumerate()
+        {
+            let full = self.prefixes.len() >= self.options.max_prefixes;
+            match self.prefixes.get_mut(&prefix) {
+                Some(stats) => stats.add(sample, rate),
+                None if !full => {
+                    let mut stats = PrefixStats::new(prefix.clone(), level + 1);
+                    stats.add(sample, rate);
+                    self.prefixes.insert(prefix, stats);
+                }
+                None => {
+                    self.other.add(sample, rate);
+                    break;
+                }
+            }
+        }
+    }
+
+    // 某个前缀的下一层前缀，按内存占用从大到小排序；prefix 为空时返回第一层
+    fn children(&self, prefix: &str) -> Vec<PrefixStats> {
+        let (level, start) = match prefix {
+            "" => (1, String::new()),
+            _ => match self.prefixes.get(prefix) {
+                Some(stats) => (stats.level + 1, format!("{}{}", prefix, self.options.separator)),
+                None => return Vec::new(),
+            },
+        };
+        let mut children: Vec<PrefixStats> = self
+            .prefixes
+            .range(start.clone()..)
+            .take_while(|(name, _)| name.starts_with(&start))
+            .filter(|(_, stats)| stats.level == level)
+            .map(|(_, stats)| stats.clone())
+            .collect();
+        children.sort_by_key(|stats| std::cmp::Reverse(stats.total_bytes));
+        children
+    }
+
+    fn csv_rows(&self) -> (Vec<String>, Vec<Vec<String>>) {
+        let rows = self
+            .prefixes
+            .values()
+            .chain(Some(&self.other).filter(|other| other.keys > 0))
+            .map(|stats| {
+                vec![
+                    stats.prefix.clone(),
+                    stats.level.to_string(),
+                    stats.keys.to_string(),
+                    stats.total_bytes.to_string(),
+                    stats.avg_bytes.to_string(),
+                    stats.estimated_keys.to_string(),
+                    stats.estimated_bytes.to_string(),
+                    format!("{:.2}", stats.no_ttl_percent),
+                    stats
+                        .types
+                        .iter()
+                        .map(|(t, n)| format!("{}={}", t, n))
+                        .collect::<Vec<_>>()
+                        .join(";"),
+                ]
+            })
+            .collect();
+        (
+            csv_header(&[
+                "prefix",
+                "level",
+                "keys",
+                "total_bytes",
+                "avg_bytes",
+                "estimated_keys",
+                "estimated_bytes",
+                "no_ttl_percent",
+                "types",
+            ]),
+            rows,
+        )
+    }
+}
+
+// 前缀钻取结果
+#[derive(Debug, Serialize)]
+pub struct PrefixDrillDown {
+    pub stats: PrefixStats,
+    pub children: Vec<PrefixStats>,
+}
+
+// 启动按前缀汇总内存占用的分析任务，返回任务 ID
+#[tauri::command]
+pub async fn start_prefix_analysis(
+    app: AppHandle,
+    connection_id: String,
+    options: BulkScanOptions,
+    prefix_options: PrefixOptions,
+    state: State<'_, AppState>,
+) -> Result<Response<String>, String> {
+    let rate = prefix_options.sample_rate;
+    if rate.is_nan() || rate <= 0.0 || rate > 1.0 {
+        return Ok(Response::error("抽样比例必须在 (0, 1] 之间".to_string()));
+    }
+
+    let job_id = new_job_id();
+    let samples = prefix_options.samples;
+    let mut sampler = Sampler::new(prefix_options.sample_rate);
+    let report = register_report(&state, &job_id, AnalysisReport::Prefixes(PrefixReport::new(prefix_options)));
+
+    let response = start_bulk_job(app, &state, &connection_id, job_id.clone(), options, move |conn, keys| {
+        let key_samples = collect_samples(conn, keys, &mut sampler, samples)?;
+        if let AnalysisReport::Prefixes(report) = &mut *report.lock().unwrap() {
+            for sample in &key_samples {
+                report.add(sample);
+            }
+        }
+        Ok(key_samples.len())
+    })?;
+
+    if !response.success {
+        state.reports.lock().unwrap().remove(&job_id);
+    }
+    Ok(response)
+}
+
+// 钻取前缀分析报告：返回指定前缀的统计及其下一层前缀，prefix 为空时从根节点开始
+#[tauri::command]
+pub async fn get_prefix_children(
+    job_id: String,
+    prefix: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<Response<PrefixDrillDown>, String> {
+    let shared = find_report(&state, &job_id);
+    let guard = shared.as_ref().map(|report| report.lock().unwrap());
+    let Some(AnalysisReport::Prefixes(report)) = guard.as_deref() else {
+        return Ok(Response::error(format!("前缀分析报告 {} 不存在", job_id)));
+    };
+
+    let prefix = prefix.unwrap_or_default();
+    let stats = if prefix.is_empty() {
+        report.root.clone()
+    } else {
+        match report.prefixes.get(&prefix) {
+            Some(stats) => stats.clone(),
+            None => return Ok(Response::error(format!("前缀 {} 不存在", prefix))),
+        }
+    };
+
+    Ok(Response::success(PrefixDrillDown {
+        children: report.children(&prefix),
+        stats,
+    }))
+}
+
+const MINUTE_MS: i64 = 60 * 1000;
+const HOUR_MS: i64 = 60 * MINUTE_MS;
+const DAY_MS: i64 = 24 * HOUR_MS;
+
+fn default_ttl_depth() -> usize {
+    1
+}
+
+fn default_windows() -> Vec<i64> {
+    vec![MINUTE_MS, HOUR_MS, DAY_MS, 7 * DAY_MS]
+}
+
+// TTL 分析参数
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TtlOptions {
+    #[serde(default = "default_separator")]
+    pub separator: String,
+    #[serde(default = "default_ttl_depth")]
+    pub depth: usize, // 按前缀拆分的层数，0 表示只统计整体
+    #[serde(default = "default_sample_rate")]
+    pub sample_rate: f64,
+    #[serde(default = "default_samples")]
+    pub samples: usize,
+    #[serde(default = "default_windows")]
+    pub windows_ms: Vec<i64>, // 预测的时间窗口（毫秒），默认 1 分钟、1 小时、1 天、7 天
+    #[serde(default = "default_max_prefixes")]
+    pub max_prefixes: usize, // 最多统计的前缀数，超出后新出现的前缀计入 other
+}
+
+// 直方图中的一个区间
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct TtlBucket {
+    pub keys: usize,
+    pub bytes: u64,
+}
+
+impl TtlBucket {
+    fn add(&mut self, bytes: u64) {
+        self.keys += 1;
+        self.bytes += bytes;
+    }
+}
+
+// 剩余过期时间直方图
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct TtlHistogram {
+    pub no_ttl: TtlBucket,
+    pub lt_1m: TtlBucket,
+    pub lt_1h: TtlBucket,
+    pub lt_1d: TtlBucket,
+    pub gt_1d: TtlBucket,
+}
+
+impl TtlHistogram {
+    fn add(&mut self, pttl: i64, bytes: u64) {
+        let bucket = match pttl {
+            p if p < 0 => &mut self.no_ttl,
+            p if p < MINUTE_MS => &mut self.lt_1m,
+            p if p < HOUR_MS => &mut self.lt_1h,
+            p if p < DAY_MS => &mut self.lt_1d,
+            _ => &mut self.gt_1d,
+        };
+        bucket.add(bytes);
+    }
+}
+
+// 某个时间窗口内将过期的键
+#[derive(Debug, Clone, Serialize)]
+pub struct ExpiryForecast {
+    pub within_ms: i64,
+    pub keys: usize,
+    pub bytes: u64,
+    pub estimated_keys: u64, // 按抽样比例估算
+    pub estimated_bytes: u64,
+}
+
+// 单个前缀的 TTL 统计
+#[derive(Debug, Clone, Serialize)]
+pub struct TtlStats {
+    pub prefix: String,
+    pub level: usize,
+    pub keys: usize,
+    pub histogram: TtlHistogram,
+    pub forecast: Vec<ExpiryForecast>,
+}
+
+impl TtlStats {
+    fn new(prefix: String, level: usize, windows_ms: &[i64]) -> Self {
+        TtlStats {
+            prefix,
+            level,
+            keys: 0,
+            histogram: TtlHistogram::default(),
+            forecast: windows_ms
+                .iter()
+                .map(|&within_ms| ExpiryForecast {
+                    within_ms,
+                    keys: 0,
+                    bytes: 0,
+                    estimated_keys: 0,
+                    estimated_bytes: 0,
+                })
+                .collect(),
+        }
+    }
+
+    fn add(&mut self, sample: &KeySample, sample_rate: f64) {
+        self.keys += 1;
+        self.histogram.add(sample.pttl, sample.bytes);
+        if sample.pttl < 0 {
+            return;
+        }
+        for window in self.forecast.iter_mut().filter(|w| sample.pttl <= w.within_ms) {
+            window.keys += 1;
+            window.bytes += sample.bytes;
+            window.estimated_keys = (window.keys as f64 / sample_rate).round() as u64;
+            window.estimated_bytes = (window.bytes as f64 / sample_rate).round() as u64;
+        }
+    }
+}
+
+// TTL 分布与过期预测报告，预测以抽样时刻为起点
+#[derive(Debug, Clone, Serialize)]
+pub struct TtlReport {
+    pub options: TtlOptions,
+    pub started_at: i64, // 任务开始的毫秒时间戳
+    pub root: TtlStats,
+    pub prefixes: BTreeMap<String, TtlStats>,
+    pub other: TtlStats, // 前缀数达到 max_prefixes 后，无法单独统计的键
+}
+
+impl TtlReport {
+    fn new(mut options: TtlOptions) -> Self {
+        options.windows_ms.retain(|&w| w > 0);
+        options.windows_ms.sort_unstable();
+        options.windows_ms.dedup();
+        TtlReport {
+            root: TtlStats::new(String::new(), 0, &options.windows_ms),
+            started_at: Utc::now().timestamp_millis(),
+            prefixes: BTreeMap::new(),
+            other: TtlStats::new(OTHER_PREFIX.to_string(), 1, &options.windows_ms),
+            options,
+        }
+    }
+
+    // 与前缀分析相同，前缀数达到上限后缺少的层级整体计入 other
+    fn add(&mut self, sample: &KeySample) {
+        let rate = self.options.sample_rate;
+        self.root.add(sample, rate);
+        for (level, prefix) in key_prefixes(&sample.key, &self.options.separator, self.options.depth)
+            .into_iter()
+            .enumerate()
+        {
+            let full = self.prefixes.len() >= self.options.max_prefixes;
+            match self.prefixes.get_mut(&prefix) {
+                Some(stats) => stats.add(sample, rate),
+                None if !full => {
+                    let mut stats = TtlStats::new(prefix.clone(), level + 1, &self.options.windows_ms);
+                    stats.add(sample, rate);
+                    self.prefixes.insert(prefix, stats);
+                }
+                None => {
+                    self.other.add(sample, rate);
+                    break;
+                }
+            }
+        }
+    }
+
+    fn csv_rows(&self) -> (Vec<String>, Vec<Vec<String>>) {
+        let mut header = csv_header(&[
+            "prefix", "level", "keys", "no_ttl", "lt_1m", "lt_1h", "lt_1d", "gt_1d",
+        ]);
+        header.extend(self.options.windows_ms.iter().map(|w| format!("expiring_within_{}ms", w)));
+
+        let rows = std::iter::once(&self.root)
+            .chain(self.prefixes.values())
+            .chain(Some(&self.other).filter(|other| other.keys > 0))
+            .map(|stats| {
+                let histogram = &stats.histogram;
+                let mut row = vec![
+                    stats.prefix.clone(),
+                    stats.level.to_string(),
+                    stats.keys.to_string(),
+                    histogram.no_ttl.keys.to_string(),
+                    histogram.lt_1m.keys.to_string(),
+                    histogram.lt_1h.keys.to_string(),
+                    histogram.lt_1d.keys.to_string(),
+                    histogram.gt_1d.keys.to_string(),
+                ];
+                row.extend(stats.forecast.iter().map(|w| w.keys.to_string()));
+                row
+            })
+            .collect();
+        (header, rows)
+    }
+}
+
+// 启动 TTL 分布与过期预测分析任务，返回任务 ID
+#[tauri::command]
+pub async fn start_ttl_analysis(
+    app: AppHandle,
+    connection_id: String,
+    options: BulkScanOptions,
+    ttl_options: TtlOptions,
+    state: State<'_, AppState>,
+) -> Result<Response<String>, String> {
+    let rate = ttl_options.sample_rate;
+    if rate.is_nan() || rate <= 0.0 || rate > 1.0 {
+        return Ok(Response::error("抽样比例必须在 (0, 1] 之间".to_string()));
+    }
+
+    let job_id = new_job_id();
+    let samples = ttl_options.samples;
+    let mut sampler = Sampler::new(rate);
+    let report = register_report(&state, &job_id, AnalysisReport::Ttl(TtlReport::new(ttl_options)));
+
+    let response = start_bulk_job(app, &state, &connection_id, job_id.clone(), options, move |conn, keys| {
+        let key_samples = collect_samples(conn, keys, &mut sampler, samples)?;
+        if let AnalysisReport::Ttl(report) = &mut *report.lock().unwrap() {
+            for sample in &key_samples {
+                report.add(sample);
+            }
+        }
+        Ok(key_samples.len())
+    })?;
+
+    if !response.success {
+        state.reports.lock().unwrap().remove(&job_id);
+    }
+    Ok(response)
+}
+
+fn default_hot_top_n() -> usize {
+    20
+}
+
+fn default_monitor_seconds() -> u64 {
+    10
+}
+
+// 热键查找参数
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HotKeyOptions {
+    #[serde(default = "default_hot_top_n")]
+    pub top_n: usize,
+    #[serde(default = "default_monitor_seconds")]
+    pub monitor_seconds: u64, // MONITOR 采样时长
+    #[serde(default)]
+    pub force_monitor: bool, // LFU 策略下也使用 MONITOR 采样
+}
+
+// 热键
+#[derive(Debug, Clone, Serialize)]
+pub struct HotKeyEntry {
+    pub key: String,
+    pub count: u64, // LFU 模式下为 OBJECT FREQ 的对数计数，MONITOR 模式下为采样期间的访问次数
+    pub commands: BTreeMap<String, u64>, // MONITOR 模式下各命令的访问次数
+}
+
+// 热键报告
+#[derive(Debug, Clone, Serialize)]
+pub struct HotKeyReport {
+    pub mode: String, // "lfu" 或 "monitor"
+    pub options: HotKeyOptions,
+    pub observed: u64, // LFU 模式下为检查的键数，MONITOR 模式下为当前库中带键的命令数
+    pub keys: Vec<HotKeyEntry>,
+}
+
+impl HotKeyReport {
+    fn new(mode: &str, options: HotKeyOptions) -> Self {
+        HotKeyReport {
+            mode: mode.to_string(),
+            options,
+            observed: 0,
+            keys: Vec::new(),
+        }
+    }
+
+    // LFU 模式：保留频率最高的 top_n 个键
+    fn add_lfu(&mut self, key: &str, freq: u64) {
+        self.observed += 1;
+        let top_n = self.options.top_n;
+        if self.keys.len() < top_n || self.keys.last().is_some_and(|last| last.count < freq) {
+            let position = self.keys.partition_point(|e| e.count >= freq);
+            self.keys.insert(
+                position,
+                HotKeyEntry {
+                    key: key.to_string(),
+                    count: freq,
+                    commands: BTreeMap::new(),
+                },
+            );
+            self.keys.truncate(top_n);
+        }
+    }
+
+    // MONITOR 模式：由访问计数生成排名
+    fn set_monitor_counts(&mut self, counts: &HashMap<String, HashMap<String, u64>>) {
+        let mut keys: Vec<HotKeyEntry> = counts
+            .iter()
+            .map(|(key, commands)| HotKeyEntry {
+                key: key.clone(),
+                count: commands.values().sum(),
+                commands: commands.iter().map(|(c, n)| (c.clone(), *n)).collect(),
+            })
+            .collect();
+        keys.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.key.cmp(&b.key)));
+        keys.truncate(self.options.top_n);
+        self.keys = keys;
+    }
+
+    fn csv_rows(&self) -> (Vec<String>, Vec<Vec<String>>) {
+        let rows = self
+            .keys
+            .iter()
+            .map(|entry| {
+                vec![
+                    entry.key.clone(),
+                    entry.count.to_string(),
+                    entry
+                        .commands
+                        .iter()
+                        .map(|(c, n)| format!("{}={}", c, n))
+                        .collect::<Vec<_>>()
+                        .join(";"),
+                ]
+            })
+            .collect();
+        (csv_header(&["key", "count", "commands"]), rows)
+    }
+}
+
+// 判断是否启用了 LFU 淘汰策略；CONFIG 被禁用时用 OBJECT FREQ 探测一个随机键
+fn is_lfu_policy(conn: &mut redis::Connection) -> bool {
+    if let Ok(config) = redis::cmd("CONFIG").arg("GET").arg("maxmemory-policy").query::<Vec<String>>(conn) {
+        if let Some(policy) = config.get(1) {
+            return policy.contains("lfu");
+        }
+    }
+    match redis::cmd("RANDOMKEY").query::<Option<String>>(conn) {
+        Ok(Some(key)) => redis::cmd("OBJECT").arg("FREQ").arg(key).query::<i64>(conn).is_ok(),
+        _ => false,
+    }
+}
+
+// 解析 MONITOR 输出的一行，例如 1339518083.107412 [0 127.0.0.1:60866] "get" "user:1"
+// 返回数据库编号和参数列表（参数按 sdscatrepr 的规则转义）
+// 转义还原为原始字节后再按 UTF-8 解码，多字节字符的 \xNN 序列可以正确还原
+fn parse_monitor_line(line: &str) -> Option<(u32, Vec<String>)> {
+    let start = line.find('[')?;
+    let end = line[start..].find(']')? + start;
+    let db = line[start + 1..end].split_whitespace().next()?.parse().ok()?;
+
+    let mut args = Vec::new();
+    let mut bytes = line[end + 1..].bytes();
+    while let Some(b) = bytes.next() {
+        if b != b'"' {
+            continue;
+        }
+        let mut arg = Vec::new();
+        loop {
+            match bytes.next()? {
+                b'"' => break,
+                b'\\' => match bytes.next()? {
+                    b'n' => arg.push(b'\n'),
+                    b'r' => arg.push(b'\r'),
+                    b't' => arg.push(b'\t'),
+                    b'a' => arg.push(0x07),
+                    b'b' => arg.push(0x08),
+                    b'x' => {
+                        let hex = [bytes.next()?, bytes.next()?];
+                        arg.push(u8::from_str_radix(std::str::from_utf8(&hex).ok()?, 16).ok()?);
+                    }
+                    other => arg.push(other),
+                },
+                other => arg.push(other),
+            }
+        }
+        args.push(String::from_utf8_lossy(&arg).into_owned());
+    }
+    Some((db, args))
+}
+
+// numkeys 形式的命令：index 处为键的个数，随后是键名
+fn numkeys_keys(args: &[String], index: usize) -> Vec<&String> {
+    let count = args.get(index).and_then(|n| n.parse::<usize>().ok()).unwrap_or(0);
+    args.iter().skip(index + 1).take(count).collect()
+}
+
+// 从命令参数中取出被访问的键，不带键的命令返回空
+fn monitor_command_keys(args: &[String]) -> Vec<&String> {
+    let Some(command) = args.first() else {
+        return Vec::new();
+    };
+    match command.to_lowercase().as_str() {
+        "ping" | "echo" | "info" | "select" | "config" | "client" | "auth" | "hello" | "monitor" | "scan"
+        | "keys" | "dbsize" | "multi" | "exec" | "discard" | "unwatch" | "flushdb" | "flushall" | "randomkey"
+        | "time" | "command" | "slowlog" | "debug" | "publish" | "subscribe" | "psubscribe" | "unsubscribe"
+        | "punsubscribe" | "spublish" | "ssubscribe" | "sunsubscribe" | "pubsub" | "script" | "function"
+        | "cluster" | "readonly" | "readwrite" | "asking" | "quit" | "reset" | "latency" | "lastsave" | "save"
+        | "bgsave" | "bgrewriteaof" | "role" | "replicaof" | "slaveof" | "swapdb" | "wait" | "waitaof" | "acl"
+        | "module" | "sync" | "psync" | "replconf" => Vec::new(),
+        // 多个键
+        "mget" | "del" | "unlink" | "exists" | "touch" | "watch" | "sinter" | "sunion" | "sdiff" | "pfcount"
+        | "sinterstore" | "sunionstore" | "sdiffstore" | "pfmerge" => args.iter().skip(1).collect(),
+        // 键值交替
+        "mset" | "msetnx" => args.iter().skip(1).step_by(2).collect(),
+        // 源键和目标键
+        "rename" | "renamenx" | "copy" | "smove" | "rpoplpush" | "brpoplpush" | "lmove" | "blmove"
+        | "zrangestore" | "geosearchstore" => args.iter().skip(1).take(2).collect(),
+        // 最后一个参数为超时时间
+        "blpop" | "brpop" | "bzpopmin" | "bzpopmax" => args.iter().skip(1).take(args.len().saturating_sub(2)).collect(),
+        // BITOP 运算 目标键 源键...
+        "bitop" => args.iter().skip(2).collect(),
+        // 目标键后跟 numkeys
+        "zunionstore" | "zinterstore" | "zdiffstore" => {
+            let mut keys: Vec<&String> = args.iter().skip(1).take(1).collect();
+            keys.extend(numkeys_keys(args, 2));
+            keys
+        }
+        "zunion" | "zinter" | "zdiff" | "zintercard" | "sintercard" | "lmpop" | "zmpop" => numkeys_keys(args, 1),
+        "blmpop" | "bzmpop" | "eval" | "evalsha" | "eval_ro" | "evalsha_ro" | "fcall" | "fcall_ro" => {
+            numkeys_keys(args, 2)
+        }
+        // STREAMS 之后前一半是键，后一半是 ID
+        "xread" | "xreadgroup" => match args.iter().position(|a| a.eq_ignore_ascii_case("streams")) {
+            Some(index) => {
+                let rest = &args[index + 1..];
+                rest.iter().take(rest.len() / 2).collect()
+            }
+            None => Vec::new(),
+        },
+        // 子命令形式，键在子命令之后；HELP、STATS 等子命令不带键
+        "object" | "memory" | "xinfo" | "xgroup" => match args.get(1).map(|sub| sub.to_lowercase()).as_deref() {
+            Some(
+                "encoding" | "freq" | "idletime" | "refcount" | "usage" | "stream" | "groups" | "consumers" | "create"
+                | "destroy" | "createconsumer" | "delconsumer" | "setid",
+            ) => args.iter().skip(2).take(1).collect(),
+            _ => Vec::new(),
+        },
+        _ => args.iter().skip(1).take(1).collect(),
+    }
+}
+
+// 限时 MONITOR 采样会话
+struct MonitorSession {
+    client: redis::Client,
+    db: u8,
+    job_id: String,
+    duration: Duration,
+    pattern: String, // 只统计匹配该模式的键
+    cancel: Arc<AtomicBool>,
+    report: SharedReport,
+}
+
+impl MonitorSession {
+    // 统计当前库中每个键被各命令访问的次数，每秒刷新一次报告和进度
+    fn run(&self, app: &AppHandle, report: &mut HotKeyReport, progress: &mut BulkProgress) -> Result<(), String> {
+        let mut conn = self.client.get_connection().map_err(|e| format!("获取连接失败: {}", e))?;
+        redis::cmd("MONITOR")
+            .query::<()>(&mut conn)
+            .map_err(|e| format!("MONITOR 失败: {}", e))?;
+
+        let mut counts: HashMap<String, HashMap<String, u64>> = HashMap::new();
+        let deadline = Instant::now() + self.duration;
+        let mut last_update = Instant::now();
+
+        while !self.cancel.load(Ordering::Relaxed) {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            if remaining.is_zero() {
+                break;
+            }
+            // 读取超时不超过 500ms，以便及时响应取消和截止时间
+            conn.set_read_timeout(Some(remaining.min(Duration::from_millis(500))))
+                .map_err(|e| format!("设置读取超时失败: {}", e))?;
+
+            match conn.recv_response() {
+                Ok(value) => {
+                    let line: String = redis::from_redis_value(&value).unwrap_or_default();
+                    progress.scanned += 1;
+                    if let Some((db, args)) = parse_monitor_line(&line) {
+                        if let (true, Some(command)) = (db == u32::from(self.db), args.first()) {
+                            let command = command.to_lowercase();
+                            for key in monitor_command_keys(&args)
+                                .into_iter()
+                                .filter(|key| self.pattern == "*" || match_pattern(&self.pattern, key))
+                            {
+                                progress.matched += 1;
+                                report.observed += 1;
+                                *counts.entry(key.clone()).or_default().entry(command.clone()).or_default() += 1;
+                            }
+                        }
+                    }
+                }
+                Err(e) if e.is_timeout() => {}
+                Err(e) => return Err(format!("读取 MONITOR 输出失败: {}", e)),
+            }
+
+            if last_update.elapsed() >= Duration::from_secs(1) {
+                last_update = Instant::now();
+                report.set_monitor_counts(&counts);
+                progress.processed = counts.len();
+                *self.report.lock().unwrap() = AnalysisReport::HotKeys(report.clone());
+                let _ = app.emit(BULK_PROGRESS_EVENT, progress.clone());
+            }
+        }
+
+        report.set_monitor_counts(&counts);
+        progress.processed = counts.len();
+        Ok(())
+    }
+}
+
+// 启动热键查找任务，返回任务 ID：LFU 淘汰策略下扫描 OBJECT FREQ，否则限时运行 MONITOR 采样
+// MONITOR 会降低服务器吞吐，采样时长应尽量短；MONITOR 模式只统计匹配 pattern 的键，不支持 key_type
+#[tauri::command]
+pub async fn start_hot_key_scan(
+    app: AppHandle,
+    connection_id: String,
+    options: BulkScanOptions,
+    hot_key_options: HotKeyOptions,
+    state: State<'_, AppState>,
+) -> Result<Response<String>, String> {
+    let (client, current_db) = {
+        let connections = state.connections.lock().unwrap();
+        match connections.get(&connection_id) {
+            Some(conn_state) => (conn_state.client.clone(), conn_state.current_db),
+            None => return Ok(Response::error("Redis 未连接".to_string())),
+        }
+    };
+
+    let lfu = !hot_key_options.force_monitor
+        && match client.get_connection() {
+            Ok(mut conn) => {
+                let _ = redis::cmd("SELECT").arg(current_db).query::<()>(&mut conn);
+                is_lfu_policy(&mut conn)
+            }
+            Err(e) => return Ok(Response::error(format!("获取连接失败: {}", e))),
+        };
+
+    let job_id = new_job_id();
+
+    if lfu {
+        let report = register_report(&state, &job_id, AnalysisReport::HotKeys(HotKeyReport::new("lfu", hot_key_options)));
+
+        let response = start_bulk_job(app, &state, &connection_id, job_id.clone(), options, move |conn, keys| {
+            let mut pipe = redis::pipe();
+            for key in keys {
+                pipe.cmd("OBJECT").arg("FREQ").arg(key);
+            }
+            let freqs: Vec<Option<u64>> = pipe.query(conn).map_err(|e| format!("OBJECT FREQ 失败: {}", e))?;
+            if let AnalysisReport::HotKeys(report) = &mut *report.lock().unwrap() {
+                for (key, freq) in keys.iter().zip(freqs) {
+                    if let Some(freq) = freq {
+                        report.add_lfu(key, freq);
+                    }
+                }
+            }
+            Ok(keys.len())
+        })?;
+
+        if !response.success {
+            state.reports.lock().unwrap().remove(&job_id);
+        }
+        return Ok(response);
+    }
+
+    // MONITOR 输出中没有键的类型，无法按类型过滤
+    if options.key_type.is_some() {
+        return Ok(Response::error("MONITOR 采样不支持按键类型过滤".to_string()));
+    }
+
+    let duration = Duration::from_secs(hot_key_options.monitor_seconds.max(1));
+    let report = HotKeyReport::new("monitor", hot_key_options);
+    let shared = register_report(&state, &job_id, AnalysisReport::HotKeys(report.clone()));
+
+    let cancel = Arc::new(AtomicBool::new(false));
+    state.jobs.lock().unwrap().insert(job_id.clone(), cancel.clone());
+    let jobs = state.jobs.clone();
+
+    let session = MonitorSession {
+        client,
+        db: current_db,
+        job_id: job_id.clone(),
+        duration,
+        pattern: options.pattern,
+        cancel,
+        report: shared,
+    };
+    thread::spawn(move || {
+        let mut report = report;
+        let mut progress = BulkProgress {
+            job_id: session.job_id.clone(),
+            scanned: 0,
+            matched: 0,
+            processed: 0,
+            done: false,
+            cancelled: false,
+            error: None,
+        };
+
+        let result = session.run(&app, &mut report, &mut progress);
+        *session.report.lock().unwrap() = AnalysisReport::HotKeys(report);
+
+        progress.done = true;
+        progress.cancelled = session.cancel.load(Ordering::Relaxed);
+        progress.error = result.err();
+        let _ = app.emit(BULK_PROGRESS_EVENT, progress);
+        jobs.lock().unwrap().remove(&session.job_id);
+    });
+
+    Ok(Response::success_with_message(
+        job_id.clone(),
+        format!("已启动 {} 秒的 MONITOR 采样任务 {}", duration.as_secs(), job_id),
+    ))
+}
+
+// 获取分析任务的报告，任务运行期间返回已处理部分的结果
+#[tauri::command]
+pub async fn get_analysis_report(
+    job_id: String,
+    state: State<'_, AppState>,
+) -> Result<Response<AnalysisReport>, String> {
+    match find_report(&state, &job_id) {
+        Some(report) => Ok(Response::success(report.lock().unwrap().clone())),
+        None => Ok(Response::error(format!("报告 {} 不存在", job_id))),
+    }
+}
+
+// 删除分析报告，任务仍在运行时一并取消
+#[tauri::command]
+pub async fn delete_analysis_report(
+    job_id: String,
+    state: State<'_, AppState>,
+) -> Result<Response<()>, String> {
+    if let Some(cancel) = state.jobs.lock().unwrap().get(&job_id) {
+        cancel.store(true, Ordering::Relaxed);
+    }
+    match state.reports.lock().unwrap().remove(&job_id) {
+        Some(_) => Ok(Response::<()>::success_empty_with_message(format!("报告 {} 已删除", job_id))),
+        None => Ok(Response::error(format!("报告 {} 不存在", job_id))),
+    }
+}
+
+// 将分析报告导出为 JSON 或 CSV 文件
+#[tauri::command]
+pub async fn export_analysis_report(
+    job_id: String,
+    path: String,
+    format: String, // "json" 或 "csv"
+    state: State<'_, AppState>,
+) -> Result<Response<()>, String> {
+    let content = {
+        let Some(shared) = find_report(&state, &job_id) else {
+            return Ok(Response::error(format!("报告 {} 不存在", job_id)));
+        };
+        let report = shared.lock().unwrap();
+        match format.as_str() {
+            "json" => match serde_json::to_string_pretty(&*report) {
+                Ok(json) => json,
+                Err(e) => return Ok(Response::error(format!("序列化报告失败: {}", e))),
+            },
+            "csv" => report.to_csv(),
+            _ => return Ok(Response::error(format!("不支持的导出格式: {}", format))),
+        }
+    };
+
+    match fs::write(&path, content) {
+        Ok(_) => Ok(Response::<()>::success_empty_with_message(format!("报告已导出到 {}", path))),
+        Err(e) => Ok(Response::error(format!("写入文件失败: {}", e))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(key: &str) -> KeySample {
+        KeySample {
+            key: key.to_string(),
+            key_type: "string".to_string(),
+            bytes: 10,
+            pttl: -1,
+        }
+    }
+
+    #[test]
+    fn parse_monitor_line_unescapes_arguments() {
+        let line = r#"1339518083.107412 [0 127.0.0.1:60866] "set" "user:1" "a\"b\\c\n""#;
+        let (db, args) = parse_monitor_line(line).unwrap();
+        assert_eq!(db, 0);
+        assert_eq!(args, vec!["set", "user:1", "a\"b\\c\n"]);
+
+        let line = r#"1339518083.107412 [12 unix:/tmp/redis.sock] "get" "\xe7\x94\xa8\xe6\x88\xb7:1""#;
+        let (db, args) = parse_monitor_line(line).unwrap();
+        assert_eq!(db, 12);
+        assert_eq!(args, vec!["get", "用户:1"]);
+    }
+
+    #[test]
+    fn parse_monitor_line_rejects_malformed_lines() {
+        assert!(parse_monitor_line("OK").is_none());
+        assert!(parse_monitor_line(r#"1339518083.107412 [x 127.0.0.1:60866] "get" "a""#).is_none());
+        assert!(parse_monitor_line(r#"1339518083.107412 [0 127.0.0.1:60866] "get" "a"#).is_none());
+        assert!(parse_monitor_line(r#"1339518083.107412 [0 127.0.0.1:60866] "get" "\xzz""#).is_none());
+    }
+
+    #[test]
+    fn monitor_command_keys_by_command() {
+        let args = |line: &str| line.split(' ').map(|s| s.to_string()).collect::<Vec<_>>();
+        assert_eq!(monitor_command_keys(&args("GET a")), vec!["a"]);
+        assert_eq!(monitor_command_keys(&args("del a b")), vec!["a", "b"]);
+        assert_eq!(monitor_command_keys(&args("mset a 1 b 2")), vec!["a", "b"]);
+        assert!(monitor_command_keys(&args("ping")).is_empty());
+    }
+
+    #[test]
+    fn monitor_command_keys_for_subcommands_and_numkeys() {
+        let args = |line: &str| line.split(' ').map(|s| s.to_string()).collect::<Vec<_>>();
+        assert_eq!(monitor_command_keys(&args("XINFO STREAM s")), vec!["s"]);
+        assert_eq!(monitor_command_keys(&args("xgroup create s g $ MKSTREAM")), vec!["s"]);
+        assert_eq!(monitor_command_keys(&args("object encoding k")), vec!["k"]);
+        assert_eq!(monitor_command_keys(&args("memory usage k SAMPLES 5")), vec!["k"]);
+        assert!(monitor_command_keys(&args("xinfo help")).is_empty());
+        assert!(monitor_command_keys(&args("memory stats")).is_empty());
+
+        assert_eq!(monitor_command_keys(&args("zunionstore d 2 a b WEIGHTS 1 2")), vec!["d", "a", "b"]);
+        assert_eq!(monitor_command_keys(&args("zinter 2 a b")), vec!["a", "b"]);
+        assert_eq!(monitor_command_keys(&args("lmpop 2 a b LEFT COUNT 1")), vec!["a", "b"]);
+        assert_eq!(monitor_command_keys(&args("blmpop 0 2 a b LEFT")), vec!["a", "b"]);
+        assert_eq!(monitor_command_keys(&args("evalsha abc 1 k arg")), vec!["k"]);
+        assert!(monitor_command_keys(&args("eval script 0")).is_empty());
+
+        assert_eq!(monitor_command_keys(&args("BLPOP a b 0")), vec!["a", "b"]);
+        assert_eq!(monitor_command_keys(&args("lmove a b LEFT RIGHT")), vec!["a", "b"]);
+        assert_eq!(monitor_command_keys(&args("bitop AND d a b")), vec!["d", "a", "b"]);
+        assert_eq!(monitor_command_keys(&args("xread COUNT 2 STREAMS a b 0 0")), vec!["a", "b"]);
+        assert_eq!(monitor_command_keys(&args("xreadgroup GROUP g c streams a >")), vec!["a"]);
+    }
+
+    #[test]
+    fn key_prefixes_stop_at_depth_and_skip_the_key_itself() {
+        assert_eq!(key_prefixes("a:b:c", ":", 3), vec!["a", "a:b"]);
+        assert_eq!(key_prefixes("a:b:c:d", ":", 2), vec!["a", "a:b"]);
+        assert_eq!(key_prefixes("a::b", ":", 3), vec!["a", "a:"]);
+        assert_eq!(key_prefixes("user/1/name", "/", 1), vec!["user"]);
+        assert!(key_prefixes("plain", ":", 3).is_empty());
+        assert!(key_prefixes("a:b", "", 3).is_empty());
+        assert!(key_prefixes("a:b", ":", 0).is_empty());
+    }
+
+    #[test]
+    fn prefix_report_folds_new_prefixes_into_other_past_the_cap() {
+        let mut report = PrefixReport::new(PrefixOptions {
+            separator: ":".to_string(),
+            depth: 2,
+            sample_rate: 1.0,
+            samples: 5,
+            max_prefixes: 2,
+        });
+        report.add(&sample("a:x:1"));
+        report.add(&sample("a:y:1"));
+        report.add(&sample("a:x:2"));
+        report.add(&sample("b:x:1"));
+
+        assert_eq!(report.prefixes.keys().collect::<Vec<_>>(), vec!["a", "a:x"]);
+        assert_eq!(report.prefixes["a"].keys, 3);
+        assert_eq!(report.prefixes["a:x"].keys, 2);
+        assert_eq!(report.other.keys, 2);
+        assert_eq!(report.root.keys, 4);
+    }
+}
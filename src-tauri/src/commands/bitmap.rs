@@ -0,0 +1,385 @@
+use crate::state::AppState;
+use crate::commands::response::Response;
+use serde::{Deserialize, Serialize};
+use tauri::State;
+
+// 单次最多渲染的字节数，避免一次性把大位图全部拉到前端
+const MAX_RANGE_BYTES: usize = 4096;
+
+// 位图区间数据
+#[derive(Debug, Serialize)]
+pub struct BitmapRange {
+    pub start_byte: usize,
+    pub byte_length: usize,
+    pub total_bytes: usize,
+    pub bits: String, // 由 '0' / '1' 组成，每个字节 8 位，高位在前
+}
+
+// BITFIELD 子操作
+#[derive(Debug, Deserialize)]
+pub struct BitfieldOperation {
+    pub op: String,       // "get" / "set" / "incrby"
+    pub encoding: String, // 例如 "u8"、"i16"
+    pub offset: String,   // 位偏移，支持 "#n" 形式按类型宽度倍数偏移
+    pub value: Option<i64>,
+}
+
+// 解析 BITCOUNT / BITPOS 的区间单位
+fn parse_bit_unit(unit: &Option<String>) -> Result<Option<&'static str>, String> {
+    match unit.as_deref().map(|u| u.to_lowercase()) {
+        None => Ok(None),
+        Some(u) if u == "byte" => Ok(Some("BYTE")),
+        Some(u) if u == "bit" => Ok(Some("BIT")),
+        Some(u) => Err(format!("不支持的区间单位: {}", u)),
+    }
+}
+
+// 按字节读取位图区间并渲染为位串
+#[tauri::command]
+pub async fn get_bitmap_range(
+    connection_id: String,
+    key: String,
+    start_byte: usize,
+    byte_length: usize,
+    state: State<'_, AppState>,
+) -> Result<Response<BitmapRange>, String> {
+    if byte_length == 0 || byte_length > MAX_RANGE_BYTES {
+        return Ok(Response::error(format!("读取长度必须在 1 到 {} 字节之间", MAX_RANGE_BYTES)));
+    }
+
+    let connections = state.connections.lock().unwrap();
+
+    if let Some(conn_state) = connections.get(&connection_id) {
+        match conn_state.client.get_connection() {
+            Ok(mut conn) => {
+                let current_db = conn_state.current_db;
+                if let Err(e) = redis::cmd("SELECT").arg(current_db).query::<()>(&mut conn) {
+                    return Ok(Response::error(format!("切换到数据库 {} 失败: {}", current_db, e)));
+                }
+
+                let total_bytes: usize = match redis::cmd("STRLEN").arg(&key).query(&mut conn) {
+                    Ok(len) => len,
+                    Err(e) => return Ok(Response::error(format!("获取位图长度失败: {}", e))),
+                };
+
+                let bytes: Vec<u8> = match redis::cmd("GETRANGE")
+                    .arg(&key)
+                    .arg(start_byte)
+                    .arg(start_byte + byte_length - 1)
+                    .query(&mut conn)
+                {
+                    Ok(bytes) => bytes,
+                    Err(e) => return Ok(Response::error(format!("读取位图失败: {}", e))),
+                };
+
+                let bits: String = bytes.iter().map(|b| format!("{:08b}", b)).collect();
+
+                Ok(Response::success(BitmapRange {
+                    start_byte,
+                    byte_length: bytes.len(),
+                    total_bytes,
+                    bits,
+                }))
+            }
+            Err(e) => Ok(Response::error(format!("获取连接失败: {}", e))),
+        }
+    } else {
+        Ok(Response::error("Redis 未连接".to_string()))
+    }
+}
+
+// 获取指定偏移的位
+#[tauri::command]
+pub async fn get_bit(
+    connection_id: String,
+    key: String,
+    offset: u64,
+    state: State<'_, AppState>,
+) -> Result<Response<u8>, String> {
+    let connections = state.connections.lock().unwrap();
+
+    if let Some(conn_state) = connections.get(&connection_id) {
+        match conn_state.client.get_connection() {
+            Ok(mut conn) => {
+                let current_db = conn_state.current_db;
+                if let Err(e) = redis::cmd("SELECT").arg(current_db).query::<()>(&mut conn) {
+                    return Ok(Response::error(format!("切换到数据库 {} 失败: {}", current_db, e)));
+                }
+
+                match redis::cmd("GETBIT").arg(&key).arg(offset).query::<u8>(&mut conn) {
+                    Ok(bit) => Ok(Response::success(bit)),
+                    Err(e) => Ok(Response::error(format!("获取位失败: {}", e))),
+                }
+            }
+            Err(e) => Ok(Response::error(format!("获取连接失败: {}", e))),
+        }
+    } else {
+        Ok(Response::error("Redis 未连接".to_string()))
+    }
+}
+
+// 设置指定偏移的位，返回原来的值
+#[tauri::command]
+pub async fn set_bit(
+    connection_id: String,
+    key: String,
+    offset: u64,
+    value: u8,
+    state: State<'_, AppState>,
+) -> Result<Response<u8>, String> {
+    if value > 1 {
+        return Ok(Response::error("位的值只能是 0 或 1".to_string()));
+    }
+
+    let connections = state.connections.lock().unwrap();
+
+    if let Some(conn_state) = connections.get(&connection_id) {
+        match conn_state.client.get_connection() {
+            Ok(mut conn) => {
+                let current_db = conn_state.current_db;
+                if let Err(e) = redis::cmd("SELECT").arg(current_db).query::<()>(&mut conn) {
+                    return Ok(Response::error(format!("切换到数据库 {} 失败: {}", current_db, e)));
+                }
+
+                match redis::cmd("SETBIT")
+                    .arg(&key)
+                    .arg(offset)
+                    .arg(value)
+                    .query::<u8>(&mut conn)
+                {
+                    Ok(old) => Ok(Response::success_with_message(old, format!("成功将 {} 的第 {} 位设置为 {}", key, offset, value))),
+                    Err(e) => Ok(Response::error(format!("设置位失败: {}", e))),
+                }
+            }
+            Err(e) => Ok(Response::error(format!("获取连接失败: {}", e))),
+        }
+    } else {
+        Ok(Response::error("Redis 未连接".to_string()))
+    }
+}
+
+// 统计区间内值为 1 的位数（BITCOUNT）
+#[tauri::command]
+pub async fn bit_count(
+    connection_id: String,
+    key: String,
+    start: Option<i64>,
+    end: Option<i64>,
+    unit: Option<String>, // "byte" 或 "bit"，默认按字节
+    state: State<'_, AppState>,
+) -> Result<Response<u64>, String> {
+    let unit_arg = match parse_bit_unit(&unit) {
+        Ok(u) => u,
+        Err(e) => return Ok(Response::error(e)),
+    };
+
+    let mut cmd = redis::cmd("BITCOUNT");
+    cmd.arg(&key);
+    match (start, end) {
+        (Some(start), Some(end)) => {
+            cmd.arg(start).arg(end);
+            if let Some(unit_arg) = unit_arg {
+                cmd.arg(unit_arg);
+            }
+        }
+        (None, None) => {}
+        _ => return Ok(Response::error("起止位置必须同时指定".to_string())),
+    }
+
+    let connections = state.connections.lock().unwrap();
+
+    if let Some(conn_state) = connections.get(&connection_id) {
+        match conn_state.client.get_connection() {
+            Ok(mut conn) => {
+                let current_db = conn_state.current_db;
+                if let Err(e) = redis::cmd("SELECT").arg(current_db).query::<()>(&mut conn) {
+                    return Ok(Response::error(format!("切换到数据库 {} 失败: {}", current_db, e)));
+                }
+
+                match cmd.query::<u64>(&mut conn) {
+                    Ok(count) => Ok(Response::success(count)),
+                    Err(e) => Ok(Response::error(format!("统计位数失败: {}", e))),
+                }
+            }
+            Err(e) => Ok(Response::error(format!("获取连接失败: {}", e))),
+        }
+    } else {
+        Ok(Response::error("Redis 未连接".to_string()))
+    }
+}
+
+// 查找第一个值为 bit 的位（BITPOS），未找到时返回 -1
+#[tauri::command]
+pub async fn bit_pos(
+    connection_id: String,
+    key: String,
+    bit: u8,
+    start: Option<i64>,
+    end: Option<i64>,
+    unit: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<Response<i64>, String> {
+    if bit > 1 {
+        return Ok(Response::error("位的值只能是 0 或 1".to_string()));
+    }
+
+    let unit_arg = match parse_bit_unit(&unit) {
+        Ok(u) => u,
+        Err(e) => return Ok(Response::error(e)),
+    };
+
+    let mut cmd = redis::cmd("BITPOS");
+    cmd.arg(&key).arg(bit);
+    if let Some(start) = start {
+        cmd.arg(start);
+        if let Some(end) = end {
+            cmd.arg(end);
+            if let Some(unit_arg) = unit_arg {
+                cmd.arg(unit_arg);
+            }
+        }
+    } else if end.is_some() {
+        return Ok(Response::error("指定结束位置时必须指定起始位置".to_string()));
+    }
+
+    let connections = state.connections.lock().unwrap();
+
+    if let Some(conn_state) = connections.get(&connection_id) {
+        match conn_state.client.get_connection() {
+            Ok(mut conn) => {
+                let current_db = conn_state.current_db;
+                if let Err(e) = redis::cmd("SELECT").arg(current_db).query::<()>(&mut conn) {
+                    return Ok(Response::error(format!("切换到数据库 {} 失败: {}", current_db, e)));
+                }
+
+                match cmd.query::<i64>(&mut conn) {
+                    Ok(pos) => Ok(Response::success(pos)),
+                    Err(e) => Ok(Response::error(format!("查找位失败: {}", e))),
+                }
+            }
+            Err(e) => Ok(Response::error(format!("获取连接失败: {}", e))),
+        }
+    } else {
+        Ok(Response::error("Redis 未连接".to_string()))
+    }
+}
+
+// 位图之间的位运算（BITOP），结果写入目标键，返回目标键的字节长度
+#[tauri::command]
+pub async fn bit_op(
+    connection_id: String,
+    op: String, // "and" / "or" / "xor" / "not"
+    destination: String,
+    keys: Vec<String>,
+    state: State<'_, AppState>,
+) -> Result<Response<usize>, String> {
+    let op_arg = match op.to_lowercase().as_str() {
+        "and" => "AND",
+        "or" => "OR",
+        "xor" => "XOR",
+        "not" => "NOT",
+        _ => return Ok(Response::error(format!("不支持的位运算: {}", op))),
+    };
+
+    if keys.is_empty() {
+        return Ok(Response::error("至少需要一个键".to_string()));
+    }
+    if op_arg == "NOT" && keys.len() != 1 {
+        return Ok(Response::error("NOT 运算只能指定一个键".to_string()));
+    }
+
+    let connections = state.connections.lock().unwrap();
+
+    if let Some(conn_state) = connections.get(&connection_id) {
+        match conn_state.client.get_connection() {
+            Ok(mut conn) => {
+                let current_db = conn_state.current_db;
+                if let Err(e) = redis::cmd("SELECT").arg(current_db).query::<()>(&mut conn) {
+                    return Ok(Response::error(format!("切换到数据库 {} 失败: {}", current_db, e)));
+                }
+
+                let mut cmd = redis::cmd("BITOP");
+                cmd.arg(op_arg).arg(&destination);
+                for key in &keys {
+                    cmd.arg(key);
+                }
+
+                match cmd.query::<usize>(&mut conn) {
+                    Ok(len) => Ok(Response::success_with_message(len, format!("结果已保存到 {}，长度 {} 字节", destination, len))),
+                    Err(e) => Ok(Response::error(format!("位运算失败: {}", e))),
+                }
+            }
+            Err(e) => Ok(Response::error(format!("获取连接失败: {}", e))),
+        }
+    } else {
+        Ok(Response::error("Redis 未连接".to_string()))
+    }
+}
+
+// 按类型读写位图中的整数字段（BITFIELD GET / SET / INCRBY）
+// 结果与操作一一对应，溢出策略为 FAIL 时溢出的操作返回 null
+#[tauri::command]
+pub async fn bitfield(
+    connection_id: String,
+    key: String,
+    operations: Vec<BitfieldOperation>,
+    overflow: Option<String>, // "wrap" / "sat" / "fail"
+    state: State<'_, AppState>,
+) -> Result<Response<Vec<Option<i64>>>, String> {
+    if operations.is_empty() {
+        return Ok(Response::error("至少需要一个操作".to_string()));
+    }
+
+    let mut cmd = redis::cmd("BITFIELD");
+    cmd.arg(&key);
+
+    if let Some(overflow) = &overflow {
+        let overflow_arg = match overflow.to_lowercase().as_str() {
+            "wrap" => "WRAP",
+            "sat" => "SAT",
+            "fail" => "FAIL",
+            _ => return Ok(Response::error(format!("不支持的溢出策略: {}", overflow))),
+        };
+        cmd.arg("OVERFLOW").arg(overflow_arg);
+    }
+
+    for operation in &operations {
+        match operation.op.to_lowercase().as_str() {
+            "get" => {
+                cmd.arg("GET").arg(&operation.encoding).arg(&operation.offset);
+            }
+            "set" | "incrby" => {
+                let value = match operation.value {
+                    Some(v) => v,
+                    None => return Ok(Response::error(format!("{} 操作缺少数值", operation.op))),
+                };
+                cmd.arg(operation.op.to_uppercase())
+                    .arg(&operation.encoding)
+                    .arg(&operation.offset)
+                    .arg(value);
+            }
+            _ => return Ok(Response::error(format!("不支持的 BITFIELD 操作: {}", operation.op))),
+        }
+    }
+
+    let connections = state.connections.lock().unwrap();
+
+    if let Some(conn_state) = connections.get(&connection_id) {
+        match conn_state.client.get_connection() {
+            Ok(mut conn) => {
+                let current_db = conn_state.current_db;
+                if let Err(e) = redis::cmd("SELECT").arg(current_db).query::<()>(&mut conn) {
+                    return Ok(Response::error(format!("切换到数据库 {} 失败: {}", current_db, e)));
+                }
+
+                match cmd.query::<Vec<Option<i64>>>(&mut conn) {
+                    Ok(results) => Ok(Response::success(results)),
+                    Err(e) => Ok(Response::error(format!("BITFIELD 执行失败: {}", e))),
+                }
+            }
+            Err(e) => Ok(Response::error(format!("获取连接失败: {}", e))),
+        }
+    } else {
+        Ok(Response::error("Redis 未连接".to_string()))
+    }
+}
@@ -0,0 +1,368 @@
+use crate::state::AppState;
+use crate::commands::response::Response;
+use crate::commands::keys::{is_unknown_command, ExpireCondition, TtlOperation};
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, State};
+
+// 批量任务进度事件名
+pub const BULK_PROGRESS_EVENT: &str = "bulk-job-progress";
+
+const DEFAULT_BATCH_SIZE: usize = 500;
+const PREVIEW_SAMPLE_SIZE: usize = 20;
+
+// 批量任务的扫描条件
+#[derive(Debug, Clone, Deserialize)]
+pub struct BulkScanOptions {
+    pub pattern: String,
+    #[serde(default)]
+    pub key_type: Option<String>, // 只处理指定类型的键，例如 "hash"
+    #[serde(default)]
+    pub batch_size: Option<usize>, // 每批 SCAN / 处理的键数，默认 500
+    #[serde(default)]
+    pub throttle_ms: Option<u64>, // 每批处理后暂停的毫秒数，避免影响线上延迟
+}
+
+impl BulkScanOptions {
+    fn batch_size(&self) -> usize {
+        self.batch_size.filter(|&n| n > 0).unwrap_or(DEFAULT_BATCH_SIZE)
+    }
+}
+
+// 试运行结果
+#[derive(Debug, Serialize)]
+pub struct BulkPreview {
+    pub total: usize,
+    pub sample: Vec<String>, // 前若干个匹配的键
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub affected: Option<usize>, // 执行后实际会发生变化的键数，只有带条件的任务会统计
+}
+
+// 试运行时统计一批键中会发生变化的数量
+type AffectedCounter<'a> = dyn FnMut(&mut redis::Connection, &[String]) -> Result<usize, String> + 'a;
+
+// 批量任务进度，通过 BULK_PROGRESS_EVENT 事件推送
+#[derive(Debug, Clone, Serialize)]
+pub struct BulkProgress {
+    pub job_id: String,
+    pub scanned: usize,   // 已扫描的键数
+    pub matched: usize,   // 符合类型过滤的键数
+    pub processed: usize, // 已处理成功的键数
+    pub done: bool,
+    pub cancelled: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+// 按 SCAN 分批遍历匹配的键，指定类型时通过 pipeline 批量 TYPE 过滤
+// 回调返回 false 时停止遍历
+pub fn scan_batches<F>(
+    conn: &mut redis::Connection,
+    options: &BulkScanOptions,
+    mut on_batch: F,
+) -> Result<(), String>
+where
+    F: FnMut(&mut redis::Connection, usize, Vec<String>) -> Result<bool, String>,
+{
+    // 指定类型时优先使用 SCAN ... TYPE（Redis 6.0+）在服务端过滤，旧版本服务器改为逐批 TYPE 过滤
+    let mut scan_type = options.key_type.is_some();
+    let mut cursor: u64 = 0;
+    loop {
+        let mut cmd = redis::cmd("SCAN");
+        cmd.arg(cursor)
+            .arg("MATCH")
+            .arg(&options.pattern)
+            .arg("COUNT")
+            .arg(options.batch_size());
+        if let (true, Some(key_type)) = (scan_type, &options.key_type) {
+            cmd.arg("TYPE").arg(key_type);
+        }
+        let (next, keys): (u64, Vec<String>) = match cmd.query(conn) {
+            Ok(r) => r,
+            Err(e) if scan_type && e.to_string().to_lowercase().contains("syntax error") => {
+                scan_type = false;
+                continue;
+            }
+            Err(e) => return Err(format!("SCAN命令失败: {}", e)),
+        };
+        cursor = next;
+
+        let scanned = keys.len();
+        let keys = match &options.key_type {
+            Some(key_type) if !scan_type && !keys.is_empty() => {
+                let mut pipe = redis::pipe();
+                for key in &keys {
+                    pipe.cmd("TYPE").arg(key);
+                }
+                let types: Vec<String> = pipe.query(conn).map_err(|e| format!("获取键类型失败: {}", e))?;
+                keys.into_iter()
+                    .zip(types)
+                    .filter(|(_, t)| t == key_type)
+                    .map(|(key, _)| key)
+                    .collect()
+            }
+            _ => keys,
+        };
+
+        if !on_batch(conn, scanned, keys)? || cursor == 0 {
+            return Ok(());
+        }
+    }
+}
+
+// 统计匹配的键数并返回样例，不做任何修改
+pub fn preview_keys(
+    state: &State<'_, AppState>,
+    connection_id: &str,
+    options: &BulkScanOptions,
+    mut count_affected: Option<&mut AffectedCounter<'_>>,
+) -> Result<Response<BulkPreview>, String> {
+    // 只复制客户端，扫描期间不占用连接表的锁
+    let (client, current_db) = {
+        let connections = state.connections.lock().unwrap();
+        match connections.get(connection_id) {
+            Some(conn_state) => (conn_state.client.clone(), conn_state.current_db),
+            None => return Ok(Response::error("Redis 未连接".to_string())),
+        }
+    };
+
+    let mut conn = match client.get_connection() {
+        Ok(conn) => conn,
+        Err(e) => return Ok(Response::error(format!("获取连接失败: {}", e))),
+    };
+    if let Err(e) = redis::cmd("SELECT").arg(current_db).query::<()>(&mut conn) {
+        return Ok(Response::error(format!("切换到数据库 {} 失败: {}", current_db, e)));
+    }
+
+    let throttle = Duration::from_millis(options.throttle_ms.unwrap_or(0));
+    let mut preview = BulkPreview {
+        total: 0,
+        sample: Vec::new(),
+        affected: count_affected.as_ref().map(|_| 0),
+    };
+    let result = scan_batches(&mut conn, options, |conn, _, keys| {
+        if let (Some(counter), Some(affected)) = (count_affected.as_mut(), preview.affected.as_mut()) {
+            if !keys.is_empty() {
+                *affected += counter(conn, &keys)?;
+            }
+        }
+        preview.total += keys.len();
+        let room = PREVIEW_SAMPLE_SIZE.saturating_sub(preview.sample.len());
+        preview.sample.extend(keys.into_iter().take(room));
+
+        if !throttle.is_zero() {
+            thread::sleep(throttle);
+        }
+        Ok(true)
+    });
+
+    match result {
+        Ok(_) => {
+            let message = match preview.affected {
+                Some(affected) => format!(
+                    "共有 {} 个键匹配 {}，其中 {} 个会发生变化",
+                    preview.total, options.pattern, affected
+                ),
+                None => format!("共有 {} 个键匹配 {}", preview.total, options.pattern),
+            };
+            Ok(Response::success_with_message(preview, message))
+        }
+        Err(e) => Ok(Response::error(e)),
+    }
+}
+
+// 生成批量任务 ID
+pub fn new_job_id() -> String {
+    format!("job-{}", Utc::now().timestamp_nanos_opt().unwrap_or_default())
+}
+
+// 在后台线程中启动批量任务，返回任务 ID；进度通过事件推送，可用 cancel_bulk_job 取消
+// action 处理一批键并返回成功处理的数量
+pub fn start_bulk_job<F>(
+    app: AppHandle,
+    state: &State<'_, AppState>,
+    connection_id: &str,
+    job_id: String,
+    options: BulkScanOptions,
+    mut action: F,
+) -> Result<Response<String>, String>
+where
+    F: FnMut(&mut redis::Connection, &[String]) -> Result<usize, String> + Send + 'static,
+{
+    // 只复制客户端，后台任务运行期间不占用连接表的锁
+    let (client, current_db) = {
+        let connections = state.connections.lock().unwrap();
+        match connections.get(connection_id) {
+            Some(conn_state) => (conn_state.client.clone(), conn_state.current_db),
+            None => return Ok(Response::error("Redis 未连接".to_string())),
+        }
+    };
+
+    let cancel = Arc::new(AtomicBool::new(false));
+    state.jobs.lock().unwrap().insert(job_id.clone(), cancel.clone());
+    let jobs = state.jobs.clone();
+
+    let id = job_id.clone();
+    thread::spawn(move || {
+        let mut progress = BulkProgress {
+            job_id: id.clone(),
+            scanned: 0,
+            matched: 0,
+            processed: 0,
+            done: false,
+            cancelled: false,
+            error: None,
+        };
+        let throttle = Duration::from_millis(options.throttle_ms.unwrap_or(0));
+
+        let result = client
+            .get_connection()
+            .map_err(|e| format!("获取连接失败: {}", e))
+            .and_then(|mut conn| {
+                redis::cmd("SELECT")
+                    .arg(current_db)
+                    .query::<()>(&mut conn)
+                    .map_err(|e| format!("切换到数据库 {} 失败: {}", current_db, e))?;
+
+                scan_batches(&mut conn, &options, |conn, scanned, keys| {
+                    if cancel.load(Ordering::Relaxed) {
+                        return Ok(false);
+                    }
+
+                    progress.scanned += scanned;
+                    progress.matched += keys.len();
+                    if !keys.is_empty() {
+                        progress.processed += action(conn, &keys)?;
+                    }
+                    let _ = app.emit(BULK_PROGRESS_EVENT, progress.clone());
+
+                    if !throttle.is_zero() {
+                        thread::sleep(throttle);
+                    }
+                    Ok(true)
+                })
+            });
+
+        progress.done = true;
+        progress.cancelled = cancel.load(Ordering::Relaxed);
+        progress.error = result.err();
+        let _ = app.emit(BULK_PROGRESS_EVENT, progress);
+        jobs.lock().unwrap().remove(&id);
+    });
+
+    Ok(Response::success_with_message(job_id.clone(), format!("已启动任务 {}", job_id)))
+}
+
+// 使用 UNLINK 在后台释放内存，旧版本服务器不支持时回退为 DEL
+fn unlink_keys(conn: &mut redis::Connection, keys: &[String]) -> Result<usize, String> {
+    match redis::cmd("UNLINK").arg(keys).query::<usize>(conn) {
+        Ok(n) => Ok(n),
+        Err(e) if is_unknown_command(&e) => redis::cmd("DEL")
+            .arg(keys)
+            .query::<usize>(conn)
+            .map_err(|e| format!("DEL 失败: {}", e)),
+        Err(e) => Err(format!("UNLINK 失败: {}", e)),
+    }
+}
+
+// 批量删除试运行：返回匹配的键数和样例
+#[tauri::command]
+pub async fn preview_delete_keys(
+    connection_id: String,
+    options: BulkScanOptions,
+    state: State<'_, AppState>,
+) -> Result<Response<BulkPreview>, String> {
+    preview_keys(&state, &connection_id, &options, None)
+}
+
+// 启动批量删除任务
+#[tauri::command]
+pub async fn start_delete_keys(
+    app: AppHandle,
+    connection_id: String,
+    options: BulkScanOptions,
+    state: State<'_, AppState>,
+) -> Result<Response<String>, String> {
+    start_bulk_job(app, &state, &connection_id, new_job_id(), options, unlink_keys)
+}
+
+// 批量读取键的剩余过期毫秒数，统计执行 TTL 操作后会发生变化的数量
+fn count_ttl_changes(
+    conn: &mut redis::Connection,
+    keys: &[String],
+    operation: &TtlOperation,
+    condition: Option<ExpireCondition>,
+) -> Result<usize, String> {
+    let mut pipe = redis::pipe();
+    for key in keys {
+        pipe.cmd("PTTL").arg(key);
+    }
+    let pttls: Vec<i64> = pipe.query(conn).map_err(|e| format!("获取TTL失败: {}", e))?;
+    let now_ms = Utc::now().timestamp_millis();
+    Ok(pttls
+        .into_iter()
+        .filter(|&pttl| operation.would_change(pttl, condition, now_ms))
+        .count())
+}
+
+// 批量 TTL 试运行：返回匹配的键数、样例以及会发生变化的键数
+#[tauri::command]
+pub async fn preview_ttl_keys(
+    connection_id: String,
+    options: BulkScanOptions,
+    operation: TtlOperation,
+    condition: Option<ExpireCondition>,
+    state: State<'_, AppState>,
+) -> Result<Response<BulkPreview>, String> {
+    if let Err(e) = operation.validate() {
+        return Ok(Response::error(e));
+    }
+
+    let mut counter = |conn: &mut redis::Connection, keys: &[String]| {
+        count_ttl_changes(conn, keys, &operation, condition)
+    };
+    preview_keys(&state, &connection_id, &options, Some(&mut counter))
+}
+
+// 启动批量 TTL 任务，进度中的 processed 为实际改变了过期时间的键数
+#[tauri::command]
+pub async fn start_ttl_keys(
+    app: AppHandle,
+    connection_id: String,
+    options: BulkScanOptions,
+    operation: TtlOperation,
+    condition: Option<ExpireCondition>,
+    state: State<'_, AppState>,
+) -> Result<Response<String>, String> {
+    if let Err(e) = operation.validate() {
+        return Ok(Response::error(e));
+    }
+
+    start_bulk_job(app, &state, &connection_id, new_job_id(), options, move |conn, keys| {
+        let mut pipe = redis::pipe();
+        for key in keys {
+            operation.append(&mut pipe, key, condition);
+        }
+        let results: Vec<i64> = pipe.query(conn).map_err(|e| format!("设置 TTL 失败: {}", e))?;
+        Ok(results.into_iter().filter(|&r| r == 1).count())
+    })
+}
+
+// 取消批量任务，任务在当前批次处理完后停止
+#[tauri::command]
+pub async fn cancel_bulk_job(
+    job_id: String,
+    state: State<'_, AppState>,
+) -> Result<Response<()>, String> {
+    match state.jobs.lock().unwrap().get(&job_id) {
+        Some(cancel) => {
+            cancel.store(true, Ordering::Relaxed);
+            Ok(Response::<()>::success_empty_with_message(format!("正在取消任务 {}", job_id)))
+        }
+        None => Ok(Response::error(format!("任务 {} 不存在或已结束", job_id))),
+    }
+}
@@ -40,13 +40,28 @@ pub async fn connect_redis(
             redis::cmd("SELECT").arg(db_index).query::<()>(&mut conn)
                 .map_err(|e| format!("切换数据库失败: {}", e))?;
 
+            // 重新连接时保留已注册的 Protobuf 描述符和映射，旧的键空间通知订阅在释放锁后停止
+            let (proto, previous_keyspace) = match connections.remove(&config.id) {
+                Some(previous) => (previous.proto, previous.keyspace),
+                None => Default::default(),
+            };
+
             connections.insert(
                 config.id.clone(),
                 ConnectionState {
-                    client: client,
+                    client,
                     current_db: db_index,
+                    proto,
+                    keyspace: None,
                 },
             );
+            drop(connections);
+
+            if let Some(subscription) = previous_keyspace {
+                if let Err(e) = subscription.shutdown() {
+                    eprintln!("{}", e);
+                }
+            }
 
             Ok(Response::<()>::success_empty_with_message(format!("成功连接到 {}", config.name)))
         }
@@ -54,17 +69,24 @@ pub async fn connect_redis(
     }
 }
 
-// 断开 Redis 连接
+// 断开 Redis 连接，键空间通知订阅随之停止并恢复服务端配置
 #[tauri::command]
 pub async fn disconnect_redis(
     connection_id: String,
     state: State<'_, AppState>,
 ) -> Result<Response<()>, String> {
-    let mut connections = state.connections.lock().unwrap();
+    let removed = state.connections.lock().unwrap().remove(&connection_id);
 
-    if connections.remove(&connection_id).is_some() {
-        Ok(Response::<()>::success_empty_with_message("连接已断开".to_string()))
-    } else {
-        Ok(Response::error("连接不存在".to_string()))
+    match removed {
+        Some(conn_state) => {
+            // 释放连接表的锁后再停止订阅并恢复服务端配置
+            if let Some(subscription) = conn_state.keyspace {
+                if let Err(e) = subscription.shutdown() {
+                    eprintln!("{}", e);
+                }
+            }
+            Ok(Response::<()>::success_empty_with_message("连接已断开".to_string()))
+        }
+        None => Ok(Response::error("连接不存在".to_string())),
     }
 }
@@ -7,6 +7,86 @@ use serde::Serialize;
 pub struct DbKeyCount {
     pub db_index: u8,
     pub key_count: usize,
+    pub expires: usize, // 设置了过期时间的键数
+    pub avg_ttl: u64,   // 带过期时间的键的平均剩余 TTL（毫秒），由服务端估算
+}
+
+impl DbKeyCount {
+    fn empty(db_index: u8) -> Self {
+        DbKeyCount {
+            db_index,
+            key_count: 0,
+            expires: 0,
+            avg_ttl: 0,
+        }
+    }
+}
+
+// 数据库索引为 u8，探测时最多检查 256 个库
+const MAX_PROBE_DBS: usize = 256;
+
+// 解析 INFO keyspace，例如 "db0:keys=12,expires=3,avg_ttl=5000"，只包含非空的库
+fn parse_keyspace_info(info: &str) -> Vec<DbKeyCount> {
+    let mut stats = Vec::new();
+
+    for line in info.lines() {
+        let Some((name, fields)) = line.trim().split_once(':') else {
+            continue;
+        };
+        let Some(db_index) = name.strip_prefix("db").and_then(|i| i.parse::<u8>().ok()) else {
+            continue;
+        };
+
+        let mut entry = DbKeyCount::empty(db_index);
+        for field in fields.split(',') {
+            match field.split_once('=') {
+                Some(("keys", v)) => entry.key_count = v.parse().unwrap_or(0),
+                Some(("expires", v)) => entry.expires = v.parse().unwrap_or(0),
+                Some(("avg_ttl", v)) => entry.avg_ttl = v.parse().unwrap_or(0),
+                _ => {}
+            }
+        }
+        stats.push(entry);
+    }
+
+    stats.sort_by_key(|entry| entry.db_index);
+    stats
+}
+
+// 一次 INFO keyspace 获取所有非空库的统计，不需要切换数据库
+fn keyspace_stats(conn: &mut redis::Connection) -> redis::RedisResult<Vec<DbKeyCount>> {
+    let info: String = redis::cmd("INFO").arg("keyspace").query(conn)?;
+    Ok(parse_keyspace_info(&info))
+}
+
+// CONFIG 不可用时（托管服务通常禁用）通过 SELECT 二分探测数据库数量
+// 该连接的当前库会被改变，调用方应使用单独的连接
+fn probe_db_count(conn: &mut redis::Connection) -> usize {
+    let selectable = |conn: &mut redis::Connection, db: usize| {
+        redis::cmd("SELECT").arg(db).query::<()>(conn).is_ok()
+    };
+
+    // 集群模式等只支持 0 号库
+    if !selectable(conn, 1) {
+        return 1;
+    }
+
+    if selectable(conn, MAX_PROBE_DBS - 1) {
+        return MAX_PROBE_DBS;
+    }
+
+    // 数据库数量 n 满足 lo <= n < hi
+    let mut lo = 2;
+    let mut hi = MAX_PROBE_DBS;
+    while hi - lo > 1 {
+        let mid = (lo + hi) / 2;
+        if selectable(conn, mid - 1) {
+            lo = mid;
+        } else {
+            hi = mid;
+        }
+    }
+    lo
 }
 
 // 获取数据库数量
@@ -20,17 +100,18 @@ pub async fn get_db_count(
     if let Some(conn_state) = connections.get(&connection_id) {
         match conn_state.client.get_connection() {
             Ok(mut conn) => {
-                match redis::cmd("CONFIG").arg("GET").arg("databases").query::<Vec<String>>(&mut conn) {
-                    Ok(config) => {
-                        let count = if config.len() >= 2 {
-                            config[1].parse::<usize>().unwrap_or(16)
-                        } else {
-                            16 // 默认16个数据库
-                        };
-                        Ok(Response::success(count))
-                    }
-                    Err(e) => Ok(Response::error(format!("获取数据库数量失败: {}", e))),
-                }
+                let configured = redis::cmd("CONFIG")
+                    .arg("GET")
+                    .arg("databases")
+                    .query::<Vec<String>>(&mut conn)
+                    .ok()
+                    .and_then(|config| config.get(1).and_then(|v| v.parse::<usize>().ok()));
+
+                let count = match configured {
+                    Some(count) => count,
+                    None => probe_db_count(&mut conn),
+                };
+                Ok(Response::success(count))
             }
             Err(e) => Ok(Response::error(format!("获取连接失败: {}", e))),
         }
@@ -50,29 +131,18 @@ pub async fn get_db_key_count(
 
     if let Some(conn_state) = connections.get(&connection_id) {
         match conn_state.client.get_connection() {
-            Ok(mut conn) => {
-                let original_db = conn_state.current_db;
-
-                let _: () = redis::cmd("SELECT")
-                    .arg(db_index)
-                    .query(&mut conn)
-                    .map_err(|e| format!("切换数据库失败: {}", e))?;
-
-                // 获取键数量
-                let result = redis::cmd("DBSIZE").query::<usize>(&mut conn);
-
-                if original_db != db_index {
-                    let _ = redis::cmd("SELECT")
-                        .arg(original_db)
-                        .query::<()>(&mut conn)
-                        .ok();
-                }
-
-                match result {
-                    Ok(count) => Ok(Response::success(count)),
-                    Err(e) => Ok(Response::error(format!("获取键数量失败: {}", e))),
+            Ok(mut conn) => match keyspace_stats(&mut conn) {
+                Ok(stats) => {
+                    // INFO keyspace 不列出空库
+                    let count = stats
+                        .iter()
+                        .find(|entry| entry.db_index == db_index)
+                        .map(|entry| entry.key_count)
+                        .unwrap_or(0);
+                    Ok(Response::success(count))
                 }
-            }
+                Err(e) => Ok(Response::error(format!("获取键数量失败: {}", e))),
+            },
             Err(e) => Ok(Response::error(format!("获取连接失败: {}", e))),
         }
     } else {
@@ -80,7 +150,7 @@ pub async fn get_db_key_count(
     }
 }
 
-// 批量获取所有数据库的键数量
+// 批量获取所有数据库的键数量，空库补 0
 #[tauri::command]
 pub async fn get_all_db_key_counts(
     connection_id: String,
@@ -91,46 +161,41 @@ pub async fn get_all_db_key_counts(
 
     if let Some(conn_state) = connections.get(&connection_id) {
         match conn_state.client.get_connection() {
-            Ok(mut conn) => {
-                let original_db = conn_state.current_db;
-                let mut results = Vec::new();
-
-                // 遍历所有数据库
-                for db_index in 0..db_count {
-                    // 切换到指定数据库
-                    if let Err(_e) = redis::cmd("SELECT")
-                        .arg(db_index)
-                        .query::<()>(&mut conn)
-                    {
-                        // 如果切换失败，记录为0
-                        results.push(DbKeyCount {
-                            db_index: db_index as u8,
-                            key_count: 0,
-                        });
-                        continue;
+            Ok(mut conn) => match keyspace_stats(&mut conn) {
+                Ok(stats) => {
+                    let mut results: Vec<DbKeyCount> = (0..db_count.min(MAX_PROBE_DBS))
+                        .map(|db_index| DbKeyCount::empty(db_index as u8))
+                        .collect();
+                    for entry in stats {
+                        if let Some(slot) = results.get_mut(entry.db_index as usize) {
+                            *slot = entry;
+                        }
                     }
-
-                    // 获取键数量
-                    let count = match redis::cmd("DBSIZE").query::<usize>(&mut conn) {
-                        Ok(count) => count,
-                        Err(_) => 0,
-                    };
-                    results.push(DbKeyCount {
-                        db_index: db_index as u8,
-                        key_count: count,
-                    });
+                    Ok(Response::success(results))
                 }
+                Err(e) => Ok(Response::error(format!("获取键数量失败: {}", e))),
+            },
+            Err(e) => Ok(Response::error(format!("获取连接失败: {}", e))),
+        }
+    } else {
+        Ok(Response::error("Redis 未连接".to_string()))
+    }
+}
 
-                // 切换回原来的数据库
-                if original_db < db_count as u8 {
-                    let _ = redis::cmd("SELECT")
-                        .arg(original_db)
-                        .query::<()>(&mut conn)
-                        .ok();
-                }
+// 获取所有非空数据库的键数、过期键数和平均 TTL
+#[tauri::command]
+pub async fn get_keyspace_stats(
+    connection_id: String,
+    state: State<'_, AppState>,
+) -> Result<Response<Vec<DbKeyCount>>, String> {
+    let connections = state.connections.lock().unwrap();
 
-                Ok(Response::success(results))
-            }
+    if let Some(conn_state) = connections.get(&connection_id) {
+        match conn_state.client.get_connection() {
+            Ok(mut conn) => match keyspace_stats(&mut conn) {
+                Ok(stats) => Ok(Response::success(stats)),
+                Err(e) => Ok(Response::error(format!("获取键空间统计失败: {}", e))),
+            },
             Err(e) => Ok(Response::error(format!("获取连接失败: {}", e))),
         }
     } else {
@@ -164,3 +229,31 @@ pub async fn select_db(
         Ok(Response::error("Redis 未连接".to_string()))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn counts(stats: &[DbKeyCount]) -> Vec<(u8, usize, usize, u64)> {
+        stats
+            .iter()
+            .map(|s| (s.db_index, s.key_count, s.expires, s.avg_ttl))
+            .collect()
+    }
+
+    #[test]
+    fn parse_keyspace_info_sorts_by_db() {
+        let info = "# Keyspace\r\ndb3:keys=5,expires=0,avg_ttl=0\r\ndb0:keys=12,expires=3,avg_ttl=5000,subexpiry=0\r\n";
+        assert_eq!(
+            counts(&parse_keyspace_info(info)),
+            vec![(0, 12, 3, 5000), (3, 5, 0, 0)]
+        );
+    }
+
+    #[test]
+    fn parse_keyspace_info_skips_unrelated_lines() {
+        let info = "# Keyspace\nredis_version:7.2.0\ndbx:keys=1\ndb300:keys=1\n\ndb1:keys=oops,expires=2\n";
+        assert_eq!(counts(&parse_keyspace_info(info)), vec![(1, 0, 2, 0)]);
+        assert!(parse_keyspace_info("# Keyspace\r\n").is_empty());
+    }
+}
@@ -0,0 +1,331 @@
+use crate::state::AppState;
+use crate::commands::response::Response;
+use crate::decoder;
+use crate::decoder::plugin::FormatterPlugin;
+use crate::decoder::protobuf::ProtoRegistry;
+use crate::decoder::{PLUGIN_PREFIX, PROTOBUF_PREFIX};
+use redis::Connection;
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager, State};
+use tauri_plugin_store::StoreExt;
+
+// 值所在位置：字符串键本身、哈希的某个字段或列表的某个元素
+#[derive(Debug, Deserialize)]
+pub struct ValueLocation {
+    pub key: String,
+    pub field: Option<String>,
+    pub index: Option<i64>,
+}
+
+// 解码结果
+#[derive(Debug, Serialize)]
+pub struct DecodedValue {
+    pub format: String,
+    pub detected: bool, // 格式是否为自动识别
+    pub text: String,
+}
+
+// 读取指定位置的原始字节
+pub fn read_raw_value(conn: &mut Connection, location: &ValueLocation) -> Result<Vec<u8>, String> {
+    let key_type = redis::cmd("TYPE")
+        .arg(&location.key)
+        .query::<String>(conn)
+        .map_err(|e| format!("获取键类型失败: {}", e))?;
+
+    let value: Option<Vec<u8>> = match key_type.as_str() {
+        "string" => redis::cmd("GET")
+            .arg(&location.key)
+            .query(conn)
+            .map_err(|e| format!("获取字符串值失败: {}", e))?,
+        "hash" => {
+            let field = location.field.as_ref().ok_or("哈希类型需要指定字段")?;
+            redis::cmd("HGET")
+                .arg(&location.key)
+                .arg(field)
+                .query(conn)
+                .map_err(|e| format!("获取哈希字段失败: {}", e))?
+        }
+        "list" => {
+            let index = location.index.ok_or("列表类型需要指定索引")?;
+            redis::cmd("LINDEX")
+                .arg(&location.key)
+                .arg(index)
+                .query(conn)
+                .map_err(|e| format!("获取列表元素失败: {}", e))?
+        }
+        "none" => return Err(format!("键 {} 不存在", location.key)),
+        _ => return Err(format!("不支持解码 {} 类型的值", key_type)),
+    };
+
+    value.ok_or_else(|| "指定位置的值不存在".to_string())
+}
+
+// 将原始字节写回指定位置，字符串键保留原有 TTL
+pub fn write_raw_value(conn: &mut Connection, location: &ValueLocation, bytes: &[u8]) -> Result<(), String> {
+    let key_type = redis::cmd("TYPE")
+        .arg(&location.key)
+        .query::<String>(conn)
+        .map_err(|e| format!("获取键类型失败: {}", e))?;
+
+    match key_type.as_str() {
+        "string" => redis::cmd("SET")
+            .arg(&location.key)
+            .arg(bytes)
+            .arg("KEEPTTL")
+            .query::<()>(conn)
+            .map_err(|e| format!("保存字符串值失败: {}", e)),
+        "hash" => {
+            let field = location.field.as_ref().ok_or("哈希类型需要指定字段")?;
+            redis::cmd("HSET")
+                .arg(&location.key)
+                .arg(field)
+                .arg(bytes)
+                .query::<()>(conn)
+                .map_err(|e| format!("保存哈希字段失败: {}", e))
+        }
+        "list" => {
+            let index = location.index.ok_or("列表类型需要指定索引")?;
+            redis::cmd("LSET")
+                .arg(&location.key)
+                .arg(index)
+                .arg(bytes)
+                .query::<()>(conn)
+                .map_err(|e| format!("保存列表元素失败: {}", e))
+        }
+        "none" => Err(format!("键 {} 不存在", location.key)),
+        _ => Err(format!("不支持编码 {} 类型的值", key_type)),
+    }
+}
+
+// 按格式解码，"protobuf:<消息类型>" 使用连接注册的描述符，其余使用内置格式
+fn decode_with(proto: &ProtoRegistry, format: &str, bytes: &[u8]) -> Result<String, String> {
+    match format.strip_prefix(PROTOBUF_PREFIX) {
+        Some(message_type) => {
+            let value = proto.decode(message_type, bytes)?;
+            serde_json::to_string_pretty(&value).map_err(|e| e.to_string())
+        }
+        None => decoder::decode(format, bytes),
+    }
+}
+
+fn encode_with(proto: &ProtoRegistry, format: &str, text: &str) -> Result<Vec<u8>, String> {
+    match format.strip_prefix(PROTOBUF_PREFIX) {
+        Some(message_type) => proto.encode(message_type, text),
+        None => decoder::encode(format, text),
+    }
+}
+
+fn find_formatter<'a>(formatters: &'a [FormatterPlugin], name: &str) -> Result<&'a FormatterPlugin, String> {
+    formatters
+        .iter()
+        .find(|f| f.name == name)
+        .ok_or_else(|| format!("未登记的格式化程序: {}", name))
+}
+
+// 获取支持的值格式列表，附带已登记的格式化程序；指定连接时附带该连接已注册的 Protobuf 消息类型
+#[tauri::command]
+pub async fn list_value_formats(
+    connection_id: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<Response<Vec<String>>, String> {
+    let mut formats: Vec<String> = decoder::BUILTIN_FORMATS.iter().map(|f| f.to_string()).collect();
+
+    formats.extend(
+        state
+            .formatters
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|f| format!("{}{}", PLUGIN_PREFIX, f.name)),
+    );
+
+    if let Some(connection_id) = connection_id {
+        let connections = state.connections.lock().unwrap();
+        if let Some(conn_state) = connections.get(&connection_id) {
+            formats.extend(
+                conn_state
+                    .proto
+                    .message_types()
+                    .into_iter()
+                    .map(|t| format!("{}{}", PROTOBUF_PREFIX, t)),
+            );
+        }
+    }
+
+    Ok(Response::success(formats))
+}
+
+// 解码指定位置的值，format 为空或 "auto" 时自动识别格式
+// 自动识别时依次使用键名匹配的 Protobuf 映射、键名匹配的格式化程序和内容特征
+#[tauri::command]
+pub async fn decode_value(
+    connection_id: String,
+    location: ValueLocation,
+    format: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<Response<DecodedValue>, String> {
+    let formatters = state.formatters.lock().unwrap().clone();
+    let connections = state.connections.lock().unwrap();
+
+    if let Some(conn_state) = connections.get(&connection_id) {
+        match conn_state.client.get_connection() {
+            Ok(mut conn) => {
+                let current_db = conn_state.current_db;
+                if let Err(e) = redis::cmd("SELECT").arg(current_db).query::<()>(&mut conn) {
+                    return Ok(Response::error(format!("切换到数据库 {} 失败: {}", current_db, e)));
+                }
+
+                let bytes = match read_raw_value(&mut conn, &location) {
+                    Ok(bytes) => bytes,
+                    Err(e) => return Ok(Response::error(e)),
+                };
+
+                let (format, detected) = match format.as_deref() {
+                    None | Some("") | Some("auto") => match conn_state.proto.message_type_for_key(&location.key) {
+                        Some(message_type) => (format!("{}{}", PROTOBUF_PREFIX, message_type), true),
+                        None => match formatters.iter().find(|f| f.matches(&location.key)) {
+                            Some(formatter) => (format!("{}{}", PLUGIN_PREFIX, formatter.name), true),
+                            None => (decoder::detect(&bytes).to_string(), true),
+                        },
+                    },
+                    Some(f) => (f.to_string(), false),
+                };
+
+                let result = match format.strip_prefix(PLUGIN_PREFIX) {
+                    Some(name) => {
+                        // 外部程序执行较慢，先释放连接锁，避免阻塞其他命令
+                        drop(connections);
+                        find_formatter(&formatters, name).and_then(|f| f.decode(&bytes))
+                    }
+                    None => decode_with(&conn_state.proto, &format, &bytes),
+                };
+
+                match result {
+                    Ok(text) => Ok(Response::success(DecodedValue { format, detected, text })),
+                    Err(e) => Ok(Response::error(e)),
+                }
+            }
+            Err(e) => Ok(Response::error(format!("获取连接失败: {}", e))),
+        }
+    } else {
+        Ok(Response::error("Redis 未连接".to_string()))
+    }
+}
+
+// 将编辑后的文本按指定格式编码并写回原位置
+#[tauri::command]
+pub async fn encode_value(
+    connection_id: String,
+    location: ValueLocation,
+    format: String,
+    text: String,
+    state: State<'_, AppState>,
+) -> Result<Response<()>, String> {
+    // 外部格式化程序在获取连接锁之前执行
+    let plugin_bytes = match format.strip_prefix(PLUGIN_PREFIX) {
+        Some(name) => {
+            let formatters = state.formatters.lock().unwrap().clone();
+            match find_formatter(&formatters, name).and_then(|f| f.encode(&text)) {
+                Ok(bytes) => Some(bytes),
+                Err(e) => return Ok(Response::error(e)),
+            }
+        }
+        None => None,
+    };
+
+    let connections = state.connections.lock().unwrap();
+
+    if let Some(conn_state) = connections.get(&connection_id) {
+        let bytes = match plugin_bytes {
+            Some(bytes) => bytes,
+            None => match encode_with(&conn_state.proto, &format, &text) {
+                Ok(bytes) => bytes,
+                Err(e) => return Ok(Response::error(e)),
+            },
+        };
+
+        match conn_state.client.get_connection() {
+            Ok(mut conn) => {
+                let current_db = conn_state.current_db;
+                if let Err(e) = redis::cmd("SELECT").arg(current_db).query::<()>(&mut conn) {
+                    return Ok(Response::error(format!("切换到数据库 {} 失败: {}", current_db, e)));
+                }
+
+                match write_raw_value(&mut conn, &location, &bytes) {
+                    Ok(_) => Ok(Response::<()>::success_empty_with_message(format!("成功以 {} 格式保存 {}", format, location.key))),
+                    Err(e) => Ok(Response::error(e)),
+                }
+            }
+            Err(e) => Ok(Response::error(format!("获取连接失败: {}", e))),
+        }
+    } else {
+        Ok(Response::error("Redis 未连接".to_string()))
+    }
+}
+
+// 设置存储文件及其中保存格式化程序的键
+const SETTINGS_STORE: &str = "settings.json";
+const FORMATTERS_KEY: &str = "formatters";
+
+// 启动时从设置存储恢复已登记的格式化程序
+pub fn load_formatters(app: &AppHandle) {
+    let store = match app.store(SETTINGS_STORE) {
+        Ok(store) => store,
+        Err(e) => {
+            eprintln!("打开设置存储失败: {}", e);
+            return;
+        }
+    };
+
+    if let Some(value) = store.get(FORMATTERS_KEY) {
+        match serde_json::from_value::<Vec<FormatterPlugin>>(value) {
+            Ok(formatters) => *app.state::<AppState>().formatters.lock().unwrap() = formatters,
+            Err(e) => eprintln!("读取格式化程序设置失败: {}", e),
+        }
+    }
+}
+
+// 保存设置中登记的格式化程序，替换已有列表并写入设置存储
+#[tauri::command]
+pub async fn set_formatters(
+    app: AppHandle,
+    formatters: Vec<FormatterPlugin>,
+    state: State<'_, AppState>,
+) -> Result<Response<()>, String> {
+    for (i, formatter) in formatters.iter().enumerate() {
+        if formatter.name.trim().is_empty() {
+            return Ok(Response::error("格式化程序名称不能为空".to_string()));
+        }
+        if formatter.decode_command.trim().is_empty() {
+            return Ok(Response::error(format!("格式化程序 {} 未配置解码命令", formatter.name)));
+        }
+        if formatter.timeout_ms == 0 {
+            return Ok(Response::error(format!("格式化程序 {} 的超时时间必须大于 0", formatter.name)));
+        }
+        if formatters[..i].iter().any(|f| f.name == formatter.name) {
+            return Ok(Response::error(format!("格式化程序名称 {} 重复", formatter.name)));
+        }
+    }
+
+    let value = match serde_json::to_value(&formatters) {
+        Ok(value) => value,
+        Err(e) => return Ok(Response::error(format!("序列化格式化程序失败: {}", e))),
+    };
+    let saved = app.store(SETTINGS_STORE).and_then(|store| {
+        store.set(FORMATTERS_KEY, value);
+        store.save()
+    });
+    if let Err(e) = saved {
+        return Ok(Response::error(format!("保存格式化程序设置失败: {}", e)));
+    }
+
+    let count = formatters.len();
+    *state.formatters.lock().unwrap() = formatters;
+    Ok(Response::<()>::success_empty_with_message(format!("已登记 {} 个格式化程序", count)))
+}
+
+// 获取已登记的格式化程序
+#[tauri::command]
+pub async fn get_formatters(state: State<'_, AppState>) -> Result<Response<Vec<FormatterPlugin>>, String> {
+    Ok(Response::success(state.formatters.lock().unwrap().clone()))
+}
@@ -0,0 +1,311 @@
+use crate::state::AppState;
+use crate::commands::response::{PageData, Response};
+use serde::{Deserialize, Serialize};
+use tauri::State;
+
+// GEOPOS 返回的经纬度，成员不存在时为 nil
+type GeoPosition = Option<(f64, f64)>;
+
+// 地理位置成员
+#[derive(Debug, Serialize)]
+pub struct GeoMember {
+    pub member: String,
+    pub longitude: Option<f64>,
+    pub latitude: Option<f64>,
+    pub geohash: Option<String>,
+}
+
+// 待添加的地理位置
+#[derive(Debug, Deserialize)]
+pub struct GeoPoint {
+    pub member: String,
+    pub longitude: f64,
+    pub latitude: f64,
+}
+
+// GEOSEARCH 查询条件：中心点二选一（成员或经纬度），范围二选一（半径或矩形）
+#[derive(Debug, Deserialize)]
+pub struct GeoSearchQuery {
+    pub from_member: Option<String>,
+    pub from_longitude: Option<f64>,
+    pub from_latitude: Option<f64>,
+    pub radius: Option<f64>,
+    pub width: Option<f64>,
+    pub height: Option<f64>,
+    pub unit: String,          // "m" / "km" / "ft" / "mi"
+    pub order: Option<String>, // "asc" / "desc"，默认 asc
+}
+
+// GEOSEARCH 结果项
+#[derive(Debug, Serialize)]
+pub struct GeoSearchItem {
+    pub member: String,
+    pub distance: f64,
+    pub longitude: f64,
+    pub latitude: f64,
+}
+
+// 校验距离单位
+fn parse_geo_unit(unit: &str) -> Option<&'static str> {
+    match unit.to_lowercase().as_str() {
+        "m" => Some("m"),
+        "km" => Some("km"),
+        "ft" => Some("ft"),
+        "mi" => Some("mi"),
+        _ => None,
+    }
+}
+
+// 分页获取地理位置成员，解码为经纬度和 geohash
+#[tauri::command]
+pub async fn get_geo_members(
+    connection_id: String,
+    key: String,
+    offset: usize,
+    count: usize,
+    state: State<'_, AppState>,
+) -> Result<Response<PageData<GeoMember>>, String> {
+    if count == 0 {
+        return Ok(Response::error("每页数量必须大于 0".to_string()));
+    }
+
+    let connections = state.connections.lock().unwrap();
+
+    if let Some(conn_state) = connections.get(&connection_id) {
+        match conn_state.client.get_connection() {
+            Ok(mut conn) => {
+                let current_db = conn_state.current_db;
+                if let Err(e) = redis::cmd("SELECT").arg(current_db).query::<()>(&mut conn) {
+                    return Ok(Response::error(format!("切换到数据库 {} 失败: {}", current_db, e)));
+                }
+
+                let total: usize = match redis::cmd("ZCARD").arg(&key).query(&mut conn) {
+                    Ok(total) => total,
+                    Err(e) => return Ok(Response::error(format!("获取成员数量失败: {}", e))),
+                };
+
+                let members: Vec<String> = match redis::cmd("ZRANGE")
+                    .arg(&key)
+                    .arg(offset)
+                    .arg(offset + count - 1)
+                    .query(&mut conn)
+                {
+                    Ok(members) => members,
+                    Err(e) => return Ok(Response::error(format!("获取成员失败: {}", e))),
+                };
+
+                if members.is_empty() {
+                    return Ok(Response::success(PageData {
+                        items: Vec::new(),
+                        total,
+                        offset,
+                        count: 0,
+                    }));
+                }
+
+                // 使用管道一次性获取坐标和 geohash
+                let mut pipe = redis::pipe();
+                pipe.cmd("GEOPOS").arg(&key);
+                for member in &members {
+                    pipe.arg(member);
+                }
+                pipe.cmd("GEOHASH").arg(&key);
+                for member in &members {
+                    pipe.arg(member);
+                }
+
+                let (positions, hashes): (Vec<GeoPosition>, Vec<Option<String>>) = match pipe.query(&mut conn) {
+                    Ok(r) => r,
+                    Err(e) => return Ok(Response::error(format!("解码地理位置失败: {}", e))),
+                };
+
+                let items: Vec<GeoMember> = members
+                    .into_iter()
+                    .enumerate()
+                    .map(|(i, member)| {
+                        let position = positions.get(i).cloned().flatten();
+                        GeoMember {
+                            member,
+                            longitude: position.map(|(lon, _)| lon),
+                            latitude: position.map(|(_, lat)| lat),
+                            geohash: hashes.get(i).cloned().flatten(),
+                        }
+                    })
+                    .collect();
+
+                Ok(Response::success(PageData {
+                    count: items.len(),
+                    items,
+                    total,
+                    offset,
+                }))
+            }
+            Err(e) => Ok(Response::error(format!("获取连接失败: {}", e))),
+        }
+    } else {
+        Ok(Response::error("Redis 未连接".to_string()))
+    }
+}
+
+// 添加地理位置成员，返回新增成员数量
+#[tauri::command]
+pub async fn geo_add(
+    connection_id: String,
+    key: String,
+    points: Vec<GeoPoint>,
+    state: State<'_, AppState>,
+) -> Result<Response<usize>, String> {
+    if points.is_empty() {
+        return Ok(Response::error("至少需要一个位置".to_string()));
+    }
+
+    let connections = state.connections.lock().unwrap();
+
+    if let Some(conn_state) = connections.get(&connection_id) {
+        match conn_state.client.get_connection() {
+            Ok(mut conn) => {
+                let current_db = conn_state.current_db;
+                if let Err(e) = redis::cmd("SELECT").arg(current_db).query::<()>(&mut conn) {
+                    return Ok(Response::error(format!("切换到数据库 {} 失败: {}", current_db, e)));
+                }
+
+                let mut cmd = redis::cmd("GEOADD");
+                cmd.arg(&key);
+                for point in &points {
+                    cmd.arg(point.longitude).arg(point.latitude).arg(&point.member);
+                }
+
+                match cmd.query::<usize>(&mut conn) {
+                    Ok(added) => Ok(Response::success_with_message(added, format!("成功添加 {} 个位置到 {}", added, key))),
+                    Err(e) => Ok(Response::error(format!("添加位置失败: {}", e))),
+                }
+            }
+            Err(e) => Ok(Response::error(format!("获取连接失败: {}", e))),
+        }
+    } else {
+        Ok(Response::error("Redis 未连接".to_string()))
+    }
+}
+
+// 计算两个成员之间的距离，任一成员不存在时返回 null
+#[tauri::command]
+pub async fn geo_dist(
+    connection_id: String,
+    key: String,
+    member1: String,
+    member2: String,
+    unit: String,
+    state: State<'_, AppState>,
+) -> Result<Response<Option<f64>>, String> {
+    let unit_arg = match parse_geo_unit(&unit) {
+        Some(u) => u,
+        None => return Ok(Response::error(format!("不支持的距离单位: {}", unit))),
+    };
+
+    let connections = state.connections.lock().unwrap();
+
+    if let Some(conn_state) = connections.get(&connection_id) {
+        match conn_state.client.get_connection() {
+            Ok(mut conn) => {
+                let current_db = conn_state.current_db;
+                if let Err(e) = redis::cmd("SELECT").arg(current_db).query::<()>(&mut conn) {
+                    return Ok(Response::error(format!("切换到数据库 {} 失败: {}", current_db, e)));
+                }
+
+                match redis::cmd("GEODIST")
+                    .arg(&key)
+                    .arg(&member1)
+                    .arg(&member2)
+                    .arg(unit_arg)
+                    .query::<Option<f64>>(&mut conn)
+                {
+                    Ok(distance) => Ok(Response::success(distance)),
+                    Err(e) => Ok(Response::error(format!("计算距离失败: {}", e))),
+                }
+            }
+            Err(e) => Ok(Response::error(format!("获取连接失败: {}", e))),
+        }
+    } else {
+        Ok(Response::error("Redis 未连接".to_string()))
+    }
+}
+
+// 按半径或矩形范围搜索成员（GEOSEARCH），结果按距离排序后分页返回
+#[tauri::command]
+pub async fn geo_search(
+    connection_id: String,
+    key: String,
+    query: GeoSearchQuery,
+    offset: usize,
+    count: usize,
+    state: State<'_, AppState>,
+) -> Result<Response<PageData<GeoSearchItem>>, String> {
+    let unit_arg = match parse_geo_unit(&query.unit) {
+        Some(u) => u,
+        None => return Ok(Response::error(format!("不支持的距离单位: {}", query.unit))),
+    };
+
+    let mut cmd = redis::cmd("GEOSEARCH");
+    cmd.arg(&key);
+
+    match (&query.from_member, query.from_longitude, query.from_latitude) {
+        (Some(member), None, None) => {
+            cmd.arg("FROMMEMBER").arg(member);
+        }
+        (None, Some(longitude), Some(latitude)) => {
+            cmd.arg("FROMLONLAT").arg(longitude).arg(latitude);
+        }
+        _ => return Ok(Response::error("必须指定中心成员或中心经纬度之一".to_string())),
+    }
+
+    match (query.radius, query.width, query.height) {
+        (Some(radius), None, None) => {
+            cmd.arg("BYRADIUS").arg(radius).arg(unit_arg);
+        }
+        (None, Some(width), Some(height)) => {
+            cmd.arg("BYBOX").arg(width).arg(height).arg(unit_arg);
+        }
+        _ => return Ok(Response::error("必须指定搜索半径或矩形宽高之一".to_string())),
+    }
+
+    let order_arg = match query.order.as_deref().map(|o| o.to_lowercase()) {
+        None => "ASC",
+        Some(o) if o == "asc" => "ASC",
+        Some(o) if o == "desc" => "DESC",
+        Some(o) => return Ok(Response::error(format!("不支持的排序方式: {}", o))),
+    };
+    cmd.arg(order_arg).arg("WITHCOORD").arg("WITHDIST");
+
+    let connections = state.connections.lock().unwrap();
+
+    if let Some(conn_state) = connections.get(&connection_id) {
+        match conn_state.client.get_connection() {
+            Ok(mut conn) => {
+                let current_db = conn_state.current_db;
+                if let Err(e) = redis::cmd("SELECT").arg(current_db).query::<()>(&mut conn) {
+                    return Ok(Response::error(format!("切换到数据库 {} 失败: {}", current_db, e)));
+                }
+
+                // 返回格式：[member, distance, [longitude, latitude]]
+                match cmd.query::<Vec<(String, f64, (f64, f64))>>(&mut conn) {
+                    Ok(results) => {
+                        let items: Vec<GeoSearchItem> = results
+                            .into_iter()
+                            .map(|(member, distance, (longitude, latitude))| GeoSearchItem {
+                                member,
+                                distance,
+                                longitude,
+                                latitude,
+                            })
+                            .collect();
+                        Ok(Response::success(PageData::paginate(items, offset, count)))
+                    }
+                    Err(e) => Ok(Response::error(format!("地理搜索失败: {}", e))),
+                }
+            }
+            Err(e) => Ok(Response::error(format!("获取连接失败: {}", e))),
+        }
+    } else {
+        Ok(Response::error("Redis 未连接".to_string()))
+    }
+}
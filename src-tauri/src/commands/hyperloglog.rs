@@ -0,0 +1,194 @@
+use crate::state::AppState;
+use crate::commands::response::Response;
+use redis::Connection;
+use serde::Serialize;
+use tauri::State;
+
+// HyperLogLog 值的头部结构：4 字节魔数 "HYLL" + 1 字节编码（0 为 dense，1 为 sparse）
+const HLL_MAGIC: &[u8] = b"HYLL";
+const HLL_HEADER_LEN: usize = 16;
+
+// HyperLogLog 信息
+#[derive(Debug, Serialize)]
+pub struct HyperLogLogInfo {
+    pub count: u64,
+    pub encoding: String, // "dense" / "sparse"
+}
+
+// 检测字符串键是否为 HyperLogLog，是则返回基数估算值和编码
+pub fn detect_hyperloglog(conn: &mut Connection, key: &str) -> Result<Option<HyperLogLogInfo>, String> {
+    let header: Vec<u8> = redis::cmd("GETRANGE")
+        .arg(key)
+        .arg(0)
+        .arg(HLL_HEADER_LEN - 1)
+        .query(conn)
+        .map_err(|e| format!("读取键头部失败: {}", e))?;
+
+    if header.len() < HLL_HEADER_LEN || !header.starts_with(HLL_MAGIC) {
+        return Ok(None);
+    }
+
+    let encoding = match header[4] {
+        0 => "dense",
+        1 => "sparse",
+        _ => return Ok(None),
+    };
+
+    // 头部合法但 PFCOUNT 失败说明只是碰巧以 HYLL 开头的普通字符串
+    match redis::cmd("PFCOUNT").arg(key).query::<u64>(conn) {
+        Ok(count) => Ok(Some(HyperLogLogInfo {
+            count,
+            encoding: encoding.to_string(),
+        })),
+        Err(_) => Ok(None),
+    }
+}
+
+// 获取 HyperLogLog 信息
+#[tauri::command]
+pub async fn get_hyperloglog_info(
+    connection_id: String,
+    key: String,
+    state: State<'_, AppState>,
+) -> Result<Response<HyperLogLogInfo>, String> {
+    let connections = state.connections.lock().unwrap();
+
+    if let Some(conn_state) = connections.get(&connection_id) {
+        match conn_state.client.get_connection() {
+            Ok(mut conn) => {
+                let current_db = conn_state.current_db;
+                if let Err(e) = redis::cmd("SELECT").arg(current_db).query::<()>(&mut conn) {
+                    return Ok(Response::error(format!("切换到数据库 {} 失败: {}", current_db, e)));
+                }
+
+                match detect_hyperloglog(&mut conn, &key) {
+                    Ok(Some(info)) => Ok(Response::success(info)),
+                    Ok(None) => Ok(Response::error(format!("键 {} 不是 HyperLogLog", key))),
+                    Err(e) => Ok(Response::error(e)),
+                }
+            }
+            Err(e) => Ok(Response::error(format!("获取连接失败: {}", e))),
+        }
+    } else {
+        Ok(Response::error("Redis 未连接".to_string()))
+    }
+}
+
+// 向 HyperLogLog 添加元素，返回内部寄存器是否发生变化
+#[tauri::command]
+pub async fn pf_add(
+    connection_id: String,
+    key: String,
+    elements: Vec<String>,
+    state: State<'_, AppState>,
+) -> Result<Response<bool>, String> {
+    let connections = state.connections.lock().unwrap();
+
+    if let Some(conn_state) = connections.get(&connection_id) {
+        match conn_state.client.get_connection() {
+            Ok(mut conn) => {
+                let current_db = conn_state.current_db;
+                if let Err(e) = redis::cmd("SELECT").arg(current_db).query::<()>(&mut conn) {
+                    return Ok(Response::error(format!("切换到数据库 {} 失败: {}", current_db, e)));
+                }
+
+                let mut cmd = redis::cmd("PFADD");
+                cmd.arg(&key);
+                for element in &elements {
+                    cmd.arg(element);
+                }
+
+                match cmd.query::<bool>(&mut conn) {
+                    Ok(changed) => Ok(Response::success_with_message(changed, format!("成功向 {} 添加 {} 个元素", key, elements.len()))),
+                    Err(e) => Ok(Response::error(format!("添加失败: {}", e))),
+                }
+            }
+            Err(e) => Ok(Response::error(format!("获取连接失败: {}", e))),
+        }
+    } else {
+        Ok(Response::error("Redis 未连接".to_string()))
+    }
+}
+
+// 估算一个或多个 HyperLogLog 并集的基数
+#[tauri::command]
+pub async fn pf_count(
+    connection_id: String,
+    keys: Vec<String>,
+    state: State<'_, AppState>,
+) -> Result<Response<u64>, String> {
+    if keys.is_empty() {
+        return Ok(Response::error("至少需要一个键".to_string()));
+    }
+
+    let connections = state.connections.lock().unwrap();
+
+    if let Some(conn_state) = connections.get(&connection_id) {
+        match conn_state.client.get_connection() {
+            Ok(mut conn) => {
+                let current_db = conn_state.current_db;
+                if let Err(e) = redis::cmd("SELECT").arg(current_db).query::<()>(&mut conn) {
+                    return Ok(Response::error(format!("切换到数据库 {} 失败: {}", current_db, e)));
+                }
+
+                let mut cmd = redis::cmd("PFCOUNT");
+                for key in &keys {
+                    cmd.arg(key);
+                }
+
+                match cmd.query::<u64>(&mut conn) {
+                    Ok(count) => Ok(Response::success(count)),
+                    Err(e) => Ok(Response::error(format!("估算基数失败: {}", e))),
+                }
+            }
+            Err(e) => Ok(Response::error(format!("获取连接失败: {}", e))),
+        }
+    } else {
+        Ok(Response::error("Redis 未连接".to_string()))
+    }
+}
+
+// 合并多个 HyperLogLog 到目标键
+#[tauri::command]
+pub async fn pf_merge(
+    connection_id: String,
+    destination: String,
+    sources: Vec<String>,
+    state: State<'_, AppState>,
+) -> Result<Response<u64>, String> {
+    if sources.is_empty() {
+        return Ok(Response::error("至少需要一个源键".to_string()));
+    }
+
+    let connections = state.connections.lock().unwrap();
+
+    if let Some(conn_state) = connections.get(&connection_id) {
+        match conn_state.client.get_connection() {
+            Ok(mut conn) => {
+                let current_db = conn_state.current_db;
+                if let Err(e) = redis::cmd("SELECT").arg(current_db).query::<()>(&mut conn) {
+                    return Ok(Response::error(format!("切换到数据库 {} 失败: {}", current_db, e)));
+                }
+
+                let mut cmd = redis::cmd("PFMERGE");
+                cmd.arg(&destination);
+                for source in &sources {
+                    cmd.arg(source);
+                }
+
+                if let Err(e) = cmd.query::<()>(&mut conn) {
+                    return Ok(Response::error(format!("合并失败: {}", e)));
+                }
+
+                // 返回合并后的基数，方便前端直接展示
+                match redis::cmd("PFCOUNT").arg(&destination).query::<u64>(&mut conn) {
+                    Ok(count) => Ok(Response::success_with_message(count, format!("成功合并到 {}", destination))),
+                    Err(e) => Ok(Response::error(format!("合并成功但估算基数失败: {}", e))),
+                }
+            }
+            Err(e) => Ok(Response::error(format!("获取连接失败: {}", e))),
+        }
+    } else {
+        Ok(Response::error("Redis 未连接".to_string()))
+    }
+}
@@ -1,9 +1,13 @@
 use crate::state::AppState;
 use crate::commands::response::Response;
-use serde::Serialize;
+use crate::commands::hyperloglog::detect_hyperloglog;
+use crate::commands::strings::{ensure_not_truncated, read_string_preview, STRING_PREVIEW_BYTES};
+use crate::decoder::protobuf::ProtoRegistry;
+use crate::decoder::PROTOBUF_PREFIX;
+use serde::{Deserialize, Serialize};
 use tauri::State;
 use serde_json::json;
-use chrono::{Utc, TimeZone};
+use chrono::Utc;
 
 // 键基本信息结构体
 #[derive(Debug, Serialize)]
@@ -20,8 +24,15 @@ pub struct KeyDetail {
     #[serde(rename = "type")]
     pub key_type: String,
     pub ttl: i64,
-    pub size: usize,
-    pub create_time: String, // ISO 8601 格式的时间字符串
+    pub pttl: i64, // 剩余过期毫秒数，-1 表示永久
+    pub expire_at: i64, // 绝对过期毫秒时间戳，-1 表示永久
+    pub size: usize, // MEMORY USAGE ... SAMPLES 0 的结果（字节）
+    #[serde(flatten)]
+    pub metadata: KeyMetadata,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub format: Option<String>, // 值的解码格式，例如 "protobuf:shop.v1.Order"
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub total_length: Option<usize>, // 字符串值只返回了预览时为完整长度
     pub value: serde_json::Value, // 根据类型存储不同的值
 }
 
@@ -32,16 +43,33 @@ pub struct KeysListData {
     pub total: usize,
 }
 
+// 将编辑后的值编码为 Protobuf 字节，值可以是 JSON 对象或 JSON 文本
+fn encode_proto(proto: &ProtoRegistry, message_type: &str, value: &serde_json::Value) -> Result<Vec<u8>, String> {
+    match value.as_str() {
+        Some(text) => proto.encode(message_type, text),
+        None => proto.encode(message_type, &value.to_string()),
+    }
+}
+
 // 设置键值命令
+// 集合类型先写入临时键，再通过 RENAME 一次性替换目标键，读取方不会看到键缺失或只写了一半
 #[tauri::command]
+#[allow(clippy::too_many_arguments)]
 pub async fn set_key(
     connection_id: String,
     key: String,
     key_type: String,
     value: serde_json::Value,
     ttl: i64,
+    version: Option<String>, // get_key_detail 返回的版本号，传入时检测并发修改
+    mode: Option<String>, // "nx" 仅在键不存在时创建，"xx" 仅在键已存在时替换，默认不限制
     state: State<'_, AppState>,
-) -> Result<Response<()>, String> {
+) -> Result<Response<EditResult>, String> {
+    let mode = match WriteMode::parse(mode.as_deref()) {
+        Ok(mode) => mode,
+        Err(e) => return Ok(Response::error(e)),
+    };
+
     let connections = state.connections.lock().unwrap();
 
     if let Some(conn_state) = connections.get(&connection_id) {
@@ -52,112 +80,150 @@ pub async fn set_key(
                     return Ok(Response::error(format!("切换到数据库 {} 失败: {}", current_db, e)));
                 }
 
-                let result = match key_type.as_str() {
+                // 键名匹配 Protobuf 映射时，字符串值和哈希字段值按消息类型编码后保存
+                let proto_type = conn_state.proto.message_type_for_key(&key);
+
+                // 集合类型的写入命令，目标为与原键同槽位的临时键
+                let temp_key = temp_key_for("replace", &key);
+                let write = match key_type.as_str() {
                     "string" => {
-                        let string_value = value.as_str().unwrap_or("");
-                        if ttl > 0 {
-                            redis::cmd("SETEX")
-                                .arg(&key)
-                                .arg(ttl)
-                                .arg(string_value)
-                                .query::<()>(&mut conn)
-                        } else {
-                            redis::cmd("SET")
-                                .arg(&key)
-                                .arg(string_value)
-                                .query::<()>(&mut conn)
+                        if mode != WriteMode::Nx {
+                            if let Err(e) = ensure_not_truncated(&mut conn, &key) {
+                                return Ok(Response::error(e));
+                            }
                         }
+                        None
                     }
                     "hash" => {
-                        let hash_map: std::collections::HashMap<String, String> = serde_json::from_value(value)
-                            .map_err(|e| format!("解析哈希值失败: {}", e))?;
-                        
-                        // 先删除旧键（如果存在）
-                        let _ = redis::cmd("DEL").arg(&key).query::<()>(&mut conn);
-                        
-                        // 使用 HSET 批量设置
+                        let hash_map: std::collections::HashMap<String, Vec<u8>> = match proto_type {
+                            Some(message_type) => {
+                                let fields: std::collections::HashMap<String, serde_json::Value> = serde_json::from_value(value.clone())
+                                    .map_err(|e| format!("解析哈希值失败: {}", e))?;
+                                let mut encoded = std::collections::HashMap::new();
+                                for (field, val) in fields {
+                                    match encode_proto(&conn_state.proto, message_type, &val) {
+                                        Ok(bytes) => encoded.insert(field, bytes),
+                                        Err(e) => return Ok(Response::error(format!("字段 {} {}", field, e))),
+                                    };
+                                }
+                                encoded
+                            }
+                            None => {
+                                let fields: std::collections::HashMap<String, String> = serde_json::from_value(value.clone())
+                                    .map_err(|e| format!("解析哈希值失败: {}", e))?;
+                                fields.into_iter().map(|(field, val)| (field, val.into_bytes())).collect()
+                            }
+                        };
+                        if hash_map.is_empty() {
+                            return Ok(Response::error("哈希至少需要一个字段".to_string()));
+                        }
+
                         let mut cmd = redis::cmd("HSET");
-                        cmd.arg(&key);
+                        cmd.arg(&temp_key);
                         for (field, val) in hash_map {
                             cmd.arg(field).arg(val);
                         }
-                        let result = cmd.query::<()>(&mut conn);
-                        
-                        // 设置 TTL
-                        if result.is_ok() && ttl > 0 {
-                            let _ = redis::cmd("EXPIRE").arg(&key).arg(ttl).query::<()>(&mut conn);
-                        }
-                        result
+                        Some(cmd)
                     }
                     "list" => {
-                        let list_items: Vec<String> = serde_json::from_value(value)
+                        let list_items: Vec<String> = serde_json::from_value(value.clone())
                             .map_err(|e| format!("解析列表值失败: {}", e))?;
-                        
-                        // 先删除旧键（如果存在）
-                        let _ = redis::cmd("DEL").arg(&key).query::<()>(&mut conn);
-                        
-                        // 使用 RPUSH 批量添加
-                        let mut cmd = redis::cmd("RPUSH");
-                        cmd.arg(&key);
-                        for item in list_items {
-                            cmd.arg(item);
+                        if list_items.is_empty() {
+                            return Ok(Response::error("列表至少需要一个元素".to_string()));
                         }
-                        let result = cmd.query::<()>(&mut conn);
-                        
-                        // 设置 TTL
-                        if result.is_ok() && ttl > 0 {
-                            let _ = redis::cmd("EXPIRE").arg(&key).arg(ttl).query::<()>(&mut conn);
-                        }
-                        result
+
+                        let mut cmd = redis::cmd("RPUSH");
+                        cmd.arg(&temp_key).arg(list_items);
+                        Some(cmd)
                     }
                     "set" => {
-                        let set_items: Vec<String> = serde_json::from_value(value)
+                        let set_items: Vec<String> = serde_json::from_value(value.clone())
                             .map_err(|e| format!("解析集合值失败: {}", e))?;
-                        
-                        // 先删除旧键（如果存在）
-                        let _ = redis::cmd("DEL").arg(&key).query::<()>(&mut conn);
-                        
-                        // 使用 SADD 批量添加
-                        let mut cmd = redis::cmd("SADD");
-                        cmd.arg(&key);
-                        for item in set_items {
-                            cmd.arg(item);
+                        if set_items.is_empty() {
+                            return Ok(Response::error("集合至少需要一个成员".to_string()));
                         }
-                        let result = cmd.query::<()>(&mut conn);
-                        
-                        // 设置 TTL
-                        if result.is_ok() && ttl > 0 {
-                            let _ = redis::cmd("EXPIRE").arg(&key).arg(ttl).query::<()>(&mut conn);
-                        }
-                        result
+
+                        let mut cmd = redis::cmd("SADD");
+                        cmd.arg(&temp_key).arg(set_items);
+                        Some(cmd)
                     }
                     "zset" => {
-                        let zset_items: Vec<(String, f64)> = serde_json::from_value(value)
+                        let zset_items: Vec<(String, f64)> = serde_json::from_value(value.clone())
                             .map_err(|e| format!("解析有序集合值失败: {}", e))?;
-                        
-                        // 先删除旧键（如果存在）
-                        let _ = redis::cmd("DEL").arg(&key).query::<()>(&mut conn);
-                        
-                        // 使用 ZADD 批量添加
+                        if zset_items.is_empty() {
+                            return Ok(Response::error("有序集合至少需要一个成员".to_string()));
+                        }
+
                         let mut cmd = redis::cmd("ZADD");
-                        cmd.arg(&key);
+                        cmd.arg(&temp_key);
                         for (member, score) in zset_items {
                             cmd.arg(score).arg(member);
                         }
-                        let result = cmd.query::<()>(&mut conn);
-                        
-                        // 设置 TTL
-                        if result.is_ok() && ttl > 0 {
-                            let _ = redis::cmd("EXPIRE").arg(&key).arg(ttl).query::<()>(&mut conn);
-                        }
-                        result
+                        Some(cmd)
                     }
                     _ => return Ok(Response::error(format!("不支持的类型: {}", key_type))),
                 };
 
-                match result {
-                    Ok(_) => Ok(Response::<()>::success_empty_with_message(format!("成功创建 {} 类型的键 {}", key_type, key))),
-                    Err(e) => Ok(Response::error(format!("设置失败: {}", e))),
+                // 最终替换目标键的命令，在 WATCH 保护下以 MULTI/EXEC 执行
+                let mut pipe = redis::pipe();
+                match write {
+                    None => {
+                        let string_value = match proto_type {
+                            Some(message_type) => match encode_proto(&conn_state.proto, message_type, &value) {
+                                Ok(bytes) => bytes,
+                                Err(e) => return Ok(Response::error(e)),
+                            },
+                            None => value.as_str().unwrap_or("").as_bytes().to_vec(),
+                        };
+                        if ttl > 0 {
+                            pipe.cmd("SETEX").arg(&key).arg(ttl).arg(&string_value).ignore();
+                        } else {
+                            pipe.cmd("SET").arg(&key).arg(&string_value).ignore();
+                        }
+                    }
+                    Some(cmd) => {
+                        // 第一步：写入临时键，同一事务中设置较短的过期时间，中途退出时临时键会自动清除
+                        let mut populate = redis::pipe();
+                        populate
+                            .atomic()
+                            .add_command(cmd)
+                            .ignore()
+                            .cmd("PEXPIRE")
+                            .arg(&temp_key)
+                            .arg(TEMP_KEY_TTL_MS)
+                            .ignore();
+                        if let Err(e) = populate.query::<()>(&mut conn) {
+                            let _ = redis::cmd("DEL").arg(&temp_key).query::<()>(&mut conn);
+                            return Ok(Response::error(format!("写入临时键失败: {}", e)));
+                        }
+
+                        // 第二步：替换时设置最终的过期时间，与 RENAME 在同一事务中执行并一并带到目标键
+                        if ttl > 0 {
+                            pipe.cmd("EXPIRE").arg(&temp_key).arg(ttl).ignore();
+                        } else {
+                            pipe.cmd("PERSIST").arg(&temp_key).ignore();
+                        }
+                        pipe.cmd("RENAME").arg(&temp_key).arg(&key).ignore();
+                    }
+                }
+
+                // 第三步：检查版本号和 NX/XX 条件后替换目标键
+                let outcome = write_with_version(&mut conn, &key, version.as_deref(), mode, &mut pipe);
+                if !matches!(outcome, Ok(WriteOutcome::Written)) && key_type != "string" {
+                    let _ = redis::cmd("DEL").arg(&temp_key).query::<()>(&mut conn);
+                }
+
+                match outcome {
+                    Ok(WriteOutcome::Written) => Ok(Response::success_with_message(
+                        EditResult::written(&mut conn, &key),
+                        format!("成功创建 {} 类型的键 {}", key_type, key),
+                    )),
+                    Ok(WriteOutcome::Conflict) => {
+                        let current = read_current_value(&mut conn, &key);
+                        Ok(EditResult::conflict(&mut conn, &key, current))
+                    }
+                    Ok(WriteOutcome::Rejected(reason)) => Ok(Response::error(reason)),
+                    Err(e) => Ok(Response::error(format!("替换键 {} 失败: {}", key, e))),
                 }
             }
             Err(e) => Ok(Response::error(format!("获取连接失败: {}", e))),
@@ -184,6 +250,18 @@ pub async fn get_key(
                     return Ok(Response::error(format!("切换到数据库 {} 失败: {}", current_db, e)));
                 }
 
+                // 大字符串只返回开头部分并标记为失败，避免调用方把预览当作完整值；完整内容通过 get_string_range 分段读取
+                match read_string_preview(&mut conn, &key) {
+                    Ok(Some((preview, total))) => {
+                        return Ok(Response::error_with_data(
+                            preview,
+                            format!("{} 共 {} 字节，仅返回前 {} 字节预览", key, total, STRING_PREVIEW_BYTES),
+                        ))
+                    }
+                    Ok(None) => {}
+                    Err(e) => return Ok(Response::error(e)),
+                }
+
                 // 使用 Option<String> 处理 nil 响应
                 match redis::cmd("GET").arg(&key).query::<Option<String>>(&mut conn) {
                     Ok(Some(value)) => Ok(Response::success_with_message(value, format!("成功获取 {}", key))),
@@ -327,11 +405,15 @@ pub async fn get_key_detail(
                 };
 
                 // 获取TTL
-                let ttl = match redis::cmd("TTL").arg(&key).query::<i64>(&mut conn) {
+                let key_ttl = match get_key_ttl_internal(&mut conn, &key) {
                     Ok(t) => t,
-                    Err(e) => {
-                        return Ok(Response::error(format!("获取TTL失败: {}", e)))
-                    }
+                    Err(e) => return Ok(Response::error(e)),
+                };
+
+                // 对象元数据需要在 DUMP / GET 等命令之前读取，否则空闲时间会被重置
+                let metadata = match get_key_metadata_internal(&mut conn, &key, &key_type) {
+                    Ok(m) => m,
+                    Err(e) => return Ok(Response::error(e)),
                 };
 
                 // 获取键大小
@@ -342,40 +424,109 @@ pub async fn get_key_detail(
                     }
                 };
 
-                // 获取创建时间（Redis不直接支持，这里使用最后修改时间作为近似值）
-                let last_modified: i64 = match redis::cmd("LASTSAVE").query(&mut conn) {
-                    Ok(t) => t,
-                    Err(_) => Utc::now().timestamp(),
+                // HyperLogLog 底层是 string 类型，原始值是二进制数据，识别后单独返回基数和编码
+                let hyperloglog = if key_type == "string" {
+                    match detect_hyperloglog(&mut conn, &key) {
+                        Ok(info) => info,
+                        Err(e) => return Ok(Response::error(e)),
+                    }
+                } else {
+                    None
+                };
+                let key_type = if hyperloglog.is_some() {
+                    "hyperloglog".to_string()
+                } else {
+                    key_type
                 };
 
-                let create_time = match Utc.timestamp_opt(last_modified, 0) {
-                    chrono::LocalResult::Single(dt) => dt,
-                    _ => Utc::now(),
+                // 键名匹配了 Protobuf 映射时，字符串值和哈希字段值解码为 JSON，解码失败时保留原始内容
+                let proto_type = match key_type.as_str() {
+                    "string" | "hash" => conn_state.proto.message_type_for_key(&key),
+                    _ => None,
+                };
+                let mut decode_error: Option<String> = None;
+                let mut truncated_from: Option<usize> = None;
+                let mut decode_proto = |bytes: Vec<u8>| -> serde_json::Value {
+                    let message_type = proto_type.unwrap_or_default();
+                    match conn_state.proto.decode(message_type, &bytes) {
+                        Ok(value) => value,
+                        Err(e) => {
+                            decode_error.get_or_insert(e);
+                            json!(String::from_utf8_lossy(&bytes))
+                        }
+                    }
                 };
 
                 // 根据类型获取值
                 let value = match key_type.as_str() {
-                    "string" => {
-                        let val: String = match redis::cmd("GET").arg(&key).query(&mut conn) {
+                    "hyperloglog" => json!(hyperloglog),
+                    "string" if proto_type.is_some() => {
+                        let val: Vec<u8> = match redis::cmd("GET").arg(&key).query(&mut conn) {
                             Ok(v) => v,
                             Err(e) => {
                                 return Ok(Response::error(format!("获取字符串值失败: {}", e)))
                             }
                         };
-                        json!(val)
+                        decode_proto(val)
+                    }
+                    "string" => {
+                        // 大字符串只返回开头部分作为预览
+                        match read_string_preview(&mut conn, &key) {
+                            Ok(Some((preview, total))) => {
+                                truncated_from = Some(total);
+                                json!(preview)
+                            }
+                            Ok(None) => {
+                                let val: String = match redis::cmd("GET").arg(&key).query(&mut conn) {
+                                    Ok(v) => v,
+                                    Err(e) => {
+                                        return Ok(Response::error(format!("获取字符串值失败: {}", e)))
+                                    }
+                                };
+                                json!(val)
+                            }
+                            Err(e) => return Ok(Response::error(e)),
+                        }
                     }
                     "hash" => {
-                        let val: Vec<(String, String)> = match redis::cmd("HGETALL").arg(&key).query(&mut conn) {
-                            Ok(v) => v,
-                            Err(e) => {
-                                return Ok(Response::error(format!("获取哈希值失败: {}", e)))
+                        // 匹配 Protobuf 映射时按字节读取字段值并逐个解码
+                        let val: Vec<(String, serde_json::Value)> = if proto_type.is_some() {
+                            match redis::cmd("HGETALL").arg(&key).query::<Vec<(String, Vec<u8>)>>(&mut conn) {
+                                Ok(v) => v.into_iter().map(|(field, value)| (field, decode_proto(value))).collect(),
+                                Err(e) => {
+                                    return Ok(Response::error(format!("获取哈希值失败: {}", e)))
+                                }
+                            }
+                        } else {
+                            match redis::cmd("HGETALL").arg(&key).query::<Vec<(String, String)>>(&mut conn) {
+                                Ok(v) => v.into_iter().map(|(field, value)| (field, json!(value))).collect(),
+                                Err(e) => {
+                                    return Ok(Response::error(format!("获取哈希值失败: {}", e)))
+                                }
                             }
                         };
-                        let hash_items: Vec<serde_json::Value> = val.into_iter().map(|(field, value)| {
-                            json!({
-                                "field": field,
-                                "value": value
-                            })
+                        // 字段过期时间（Redis 7.4+ / Valkey 9+），服务端不支持时不返回 ttl 和 expire_at
+                        let field_ttls = match get_hash_field_ttls_internal(
+                            &mut conn,
+                            &key,
+                            val.iter().map(|(field, _)| field.as_str()),
+                        ) {
+                            Ok(ttls) => ttls,
+                            Err(e) => return Ok(Response::error(e)),
+                        };
+                        let hash_items: Vec<serde_json::Value> = val.into_iter().enumerate().map(|(i, (field, value))| {
+                            match &field_ttls {
+                                Some((ttls, expire_ats)) => json!({
+                                    "field": field,
+                                    "value": value,
+                                    "ttl": ttls.get(i).copied().unwrap_or(-1),
+                                    "expire_at": expire_ats.get(i).copied().unwrap_or(-1)
+                                }),
+                                None => json!({
+                                    "field": field,
+                                    "value": value
+                                }),
+                            }
                         }).collect();
                         json!(hash_items)
                     }
@@ -415,14 +566,65 @@ pub async fn get_key_detail(
                     _ => json!(null),
                 };
 
-                Ok(Response::success(KeyDetail {
+                let format = match (proto_type, &decode_error) {
+                    (Some(message_type), None) => Some(format!("{}{}", PROTOBUF_PREFIX, message_type)),
+                    _ => None,
+                };
+
+                let detail = KeyDetail {
                     key: key.clone(),
                     key_type,
-                    ttl,
+                    ttl: key_ttl.ttl,
+                    pttl: key_ttl.pttl,
+                    expire_at: key_ttl.expire_at,
                     size,
-                    create_time: create_time.to_rfc3339(),
+                    metadata,
+                    format,
+                    total_length: truncated_from,
                     value,
-                }))
+                };
+
+                if let Some(total) = truncated_from {
+                    return Ok(Response::success_with_message(
+                        detail,
+                        format!("{} 共 {} 字节，仅返回前 {} 字节预览", key, total, STRING_PREVIEW_BYTES),
+                    ));
+                }
+
+                match decode_error {
+                    Some(e) => Ok(Response::success_with_message(detail, e)),
+                    None => Ok(Response::success(detail)),
+                }
+            }
+            Err(e) => Ok(Response::error(format!("获取连接失败: {}", e))),
+        }
+    } else {
+        Ok(Response::error("Redis 未连接".to_string()))
+    }
+}
+
+// 获取键的版本号，开始编辑时调用，保存时传回用于检测并发修改
+// 计算版本号需要在服务端 DUMP 整个值，只在编辑时按需获取，不在读取详情时计算
+#[tauri::command]
+pub async fn get_key_version(
+    connection_id: String,
+    key: String,
+    state: State<'_, AppState>,
+) -> Result<Response<String>, String> {
+    let connections = state.connections.lock().unwrap();
+
+    if let Some(conn_state) = connections.get(&connection_id) {
+        match conn_state.client.get_connection() {
+            Ok(mut conn) => {
+                let current_db = conn_state.current_db;
+                if let Err(e) = redis::cmd("SELECT").arg(current_db).query::<()>(&mut conn) {
+                    return Ok(Response::error(format!("切换到数据库 {} 失败: {}", current_db, e)));
+                }
+
+                match get_version_token(&mut conn, &key) {
+                    Ok(version) => Ok(Response::success(version)),
+                    Err(e) => Ok(Response::error(e)),
+                }
             }
             Err(e) => Ok(Response::error(format!("获取连接失败: {}", e))),
         }
@@ -433,8 +635,8 @@ pub async fn get_key_detail(
 
 // 获取键大小（内部函数）
 fn get_key_size_internal(conn: &mut redis::Connection, key: &str) -> Result<usize, String> {
-    // 尝试使用 MEMORY USAGE 命令
-    if let Ok(size) = redis::cmd("MEMORY").arg("USAGE").arg(key).query::<usize>(conn) {
+    // 尝试使用 MEMORY USAGE 命令，SAMPLES 0 统计集合的全部元素而不是抽样估算
+    if let Ok(size) = redis::cmd("MEMORY").arg("USAGE").arg(key).arg("SAMPLES").arg(0).query::<usize>(conn) {
         return Ok(size);
     }
 
@@ -457,6 +659,298 @@ fn get_key_size_internal(conn: &mut redis::Connection, key: &str) -> Result<usiz
     }
 }
 
+// 键的对象元数据，服务端不支持或策略不允许的项为空
+#[derive(Debug, Serialize)]
+pub struct KeyMetadata {
+    pub encoding: Option<String>, // OBJECT ENCODING，例如 listpack、hashtable
+    pub idle_time: Option<i64>,   // OBJECT IDLETIME（秒），LFU 淘汰策略下为空
+    pub lfu_freq: Option<i64>,    // OBJECT FREQ，只在 LFU 淘汰策略下可用
+    pub refcount: Option<i64>,    // OBJECT REFCOUNT
+    pub length: Option<usize>,    // 元素个数，字符串为字节长度
+}
+
+// 按类型选择统计元素个数的命令
+pub fn length_command(key_type: &str) -> Option<&'static str> {
+    match key_type {
+        "string" | "hyperloglog" => Some("STRLEN"),
+        "hash" => Some("HLEN"),
+        "list" => Some("LLEN"),
+        "set" => Some("SCARD"),
+        "zset" => Some("ZCARD"),
+        "stream" => Some("XLEN"),
+        _ => None,
+    }
+}
+
+// 读取键的对象元数据；IDLETIME 和 FREQ 取决于 maxmemory-policy，只有一个可用
+pub fn get_key_metadata_internal(
+    conn: &mut redis::Connection,
+    key: &str,
+    key_type: &str,
+) -> Result<KeyMetadata, String> {
+    let encoding = redis::cmd("OBJECT").arg("ENCODING").arg(key).query::<Option<String>>(conn).ok().flatten();
+    let idle_time = redis::cmd("OBJECT").arg("IDLETIME").arg(key).query::<Option<i64>>(conn).ok().flatten();
+    let lfu_freq = match idle_time {
+        Some(_) => None,
+        None => redis::cmd("OBJECT").arg("FREQ").arg(key).query::<Option<i64>>(conn).ok().flatten(),
+    };
+    let refcount = redis::cmd("OBJECT").arg("REFCOUNT").arg(key).query::<Option<i64>>(conn).ok().flatten();
+
+    let length = match length_command(key_type) {
+        Some(command) => Some(
+            redis::cmd(command)
+                .arg(key)
+                .query::<usize>(conn)
+                .map_err(|e| format!("获取元素个数失败: {}", e))?,
+        ),
+        None => None,
+    };
+
+    Ok(KeyMetadata {
+        encoding,
+        idle_time,
+        lfu_freq,
+        refcount,
+        length,
+    })
+}
+
+// 判断错误是否为服务端不认识该命令（旧版本 Redis 或被重命名/禁用的命令）
+pub fn is_unknown_command(e: &redis::RedisError) -> bool {
+    let message = e.to_string().to_lowercase();
+    message.contains("unknown command") || message.contains("unknown subcommand")
+}
+
+// 取 DUMP 结果末尾的 RDB 版本号和 CRC64 校验和作为值的版本号，只在服务端截取，避免传输整个值
+const VERSION_SCRIPT: &str = "local d = redis.call('DUMP', KEYS[1]) if not d then return '' end return string.sub(d, -10)";
+
+// 计算键的版本号，值（不含 TTL）变化时版本号随之变化；键不存在时为 "none"
+pub fn get_version_token(conn: &mut redis::Connection, key: &str) -> Result<String, String> {
+    let tail: Vec<u8> = match redis::cmd("EVAL").arg(VERSION_SCRIPT).arg(1).arg(key).query(conn) {
+        Ok(tail) => tail,
+        // 只有脚本被禁用（EVAL 被重命名、ACL 不允许或服务端不支持脚本）时回退为直接 DUMP，其他错误照常返回
+        Err(e) if is_unknown_command(&e) || matches!(e.code(), Some("NOSCRIPT") | Some("NOPERM")) => {
+            let dump: Option<Vec<u8>> = redis::cmd("DUMP")
+                .arg(key)
+                .query(conn)
+                .map_err(|e| format!("获取版本号失败: {}", e))?;
+            dump.map(|d| d[d.len().saturating_sub(10)..].to_vec()).unwrap_or_default()
+        }
+        Err(e) => return Err(format!("获取版本号失败: {}", e)),
+    };
+
+    if tail.is_empty() {
+        Ok("none".to_string())
+    } else {
+        Ok(hex::encode(tail))
+    }
+}
+
+// 临时键的过期时间，写入临时键到替换目标键之间进程退出时由服务端清除
+pub const TEMP_KEY_TTL_MS: i64 = 60_000;
+
+// 键名中决定集群槽位的部分：有效的 hash tag，没有时为整个键名
+// 键名包含 '}' 且没有有效 hash tag 时无法为其构造同槽位的 hash tag，返回 None
+fn slot_tag(key: &str) -> Option<&str> {
+    if let Some(start) = key.find('{') {
+        if let Some(len) = key[start + 1..].find('}') {
+            if len > 0 {
+                return Some(&key[start + 1..start + 1 + len]);
+            }
+        }
+    }
+    if key.contains('}') {
+        None
+    } else {
+        Some(key)
+    }
+}
+
+// 生成与目标键位于同一集群槽位的临时键，保证 RENAME 到目标键时不会跨槽
+pub fn temp_key_for(kind: &str, key: &str) -> String {
+    let nanos = Utc::now().timestamp_nanos_opt().unwrap_or_default();
+    match slot_tag(key) {
+        Some(tag) => format!("__byte_redis_{}__:{{{}}}:{}", kind, tag, nanos),
+        None => format!("__byte_redis_{}__:{}", kind, nanos),
+    }
+}
+
+// 写入模式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WriteMode {
+    Any,
+    Nx, // 仅在键不存在时创建
+    Xx, // 仅在键已存在时替换
+}
+
+impl WriteMode {
+    pub fn parse(mode: Option<&str>) -> Result<Self, String> {
+        match mode.map(|m| m.to_ascii_lowercase()).as_deref() {
+            None | Some("") => Ok(WriteMode::Any),
+            Some("nx") => Ok(WriteMode::Nx),
+            Some("xx") => Ok(WriteMode::Xx),
+            Some(other) => Err(format!("不支持的写入模式: {}", other)),
+        }
+    }
+}
+
+// 受保护写入的结果
+#[derive(Debug)]
+enum WriteOutcome {
+    Written,
+    Conflict,         // 版本不一致或事务因键被修改而中断
+    Rejected(String), // 不满足 NX/XX 条件
+}
+
+// 执行写入 pipeline；传入版本号或 NX/XX 条件时先 WATCH 并校验，再以 MULTI/EXEC 执行
+fn write_with_version(
+    conn: &mut redis::Connection,
+    key: &str,
+    version: Option<&str>,
+    mode: WriteMode,
+    pipe: &mut redis::Pipeline,
+) -> Result<WriteOutcome, String> {
+    if version.is_none() && mode == WriteMode::Any {
+        return pipe
+            .atomic()
+            .query::<()>(conn)
+            .map(|_| WriteOutcome::Written)
+            .map_err(|e| e.to_string());
+    }
+
+    redis::cmd("WATCH").arg(key).query::<()>(conn).map_err(|e| e.to_string())?;
+
+    let rejected = match check_write_guard(conn, key, version, mode) {
+        Ok(rejected) => rejected,
+        Err(e) => {
+            let _ = redis::cmd("UNWATCH").query::<()>(conn);
+            return Err(e);
+        }
+    };
+    if let Some(outcome) = rejected {
+        let _ = redis::cmd("UNWATCH").query::<()>(conn);
+        return Ok(outcome);
+    }
+
+    pipe.atomic()
+        .query::<Option<redis::Value>>(conn)
+        .map(|result| match result {
+            Some(_) => WriteOutcome::Written,
+            None => WriteOutcome::Conflict,
+        })
+        .map_err(|e| e.to_string())
+}
+
+// 在 WATCH 之后校验 NX/XX 条件和版本号，不满足时返回对应的结果
+fn check_write_guard(
+    conn: &mut redis::Connection,
+    key: &str,
+    version: Option<&str>,
+    mode: WriteMode,
+) -> Result<Option<WriteOutcome>, String> {
+    if mode != WriteMode::Any {
+        let exists: bool = redis::cmd("EXISTS")
+            .arg(key)
+            .query(conn)
+            .map_err(|e| format!("检查键存在失败: {}", e))?;
+        match (mode, exists) {
+            (WriteMode::Nx, true) => return Ok(Some(WriteOutcome::Rejected(format!("键 {} 已存在", key)))),
+            (WriteMode::Xx, false) => return Ok(Some(WriteOutcome::Rejected(format!("键 {} 不存在", key)))),
+            _ => {}
+        }
+    }
+
+    if let Some(expected) = version {
+        if get_version_token(conn, key)? != expected {
+            return Ok(Some(WriteOutcome::Conflict));
+        }
+    }
+
+    Ok(None)
+}
+
+// 读取键的当前值，结构与 set_key 的入参一致
+fn read_current_value(conn: &mut redis::Connection, key: &str) -> serde_json::Value {
+    let key_type = redis::cmd("TYPE").arg(key).query::<String>(conn).unwrap_or_default();
+    let value = match key_type.as_str() {
+        "string" => redis::cmd("GET")
+            .arg(key)
+            .query::<Vec<u8>>(conn)
+            .map(|v| json!(String::from_utf8_lossy(&v))),
+        "hash" => redis::cmd("HGETALL")
+            .arg(key)
+            .query::<std::collections::HashMap<String, String>>(conn)
+            .map(|v| json!(v)),
+        "list" => redis::cmd("LRANGE").arg(key).arg(0).arg(-1).query::<Vec<String>>(conn).map(|v| json!(v)),
+        "set" => redis::cmd("SMEMBERS").arg(key).query::<Vec<String>>(conn).map(|v| json!(v)),
+        "zset" => redis::cmd("ZRANGE")
+            .arg(key)
+            .arg(0)
+            .arg(-1)
+            .arg("WITHSCORES")
+            .query::<Vec<(String, f64)>>(conn)
+            .map(|v| json!(v)),
+        _ => Ok(serde_json::Value::Null),
+    };
+    value.unwrap_or(serde_json::Value::Null)
+}
+
+// 编辑结果
+#[derive(Debug, Serialize)]
+pub struct EditResult {
+    pub conflict: bool,
+    pub version: Option<String>, // 写入后的版本号；冲突时为服务器上的当前版本号
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub current_value: Option<serde_json::Value>, // 冲突时服务器上的当前值
+}
+
+impl EditResult {
+    fn written(conn: &mut redis::Connection, key: &str) -> Self {
+        EditResult {
+            conflict: false,
+            version: get_version_token(conn, key).ok(),
+            current_value: None,
+        }
+    }
+
+    fn conflict(conn: &mut redis::Connection, key: &str, current_value: serde_json::Value) -> Response<Self> {
+        Response::error_with_data(
+            EditResult {
+                conflict: true,
+                version: get_version_token(conn, key).ok(),
+                current_value: Some(current_value),
+            },
+            format!("键 {} 已被其他客户端修改，请刷新后重试", key),
+        )
+    }
+}
+
+// 哈希字段的剩余过期秒数（HTTL）和绝对过期毫秒时间戳（HPEXPIRETIME），与字段一一对应
+type FieldTtls = (Vec<i64>, Vec<i64>);
+
+// 批量获取哈希字段的剩余过期秒数（HTTL）和绝对过期毫秒时间戳（HPEXPIRETIME）
+// 返回值中 -1 表示字段没有过期时间，-2 表示字段不存在；服务端不支持时返回 None，其他错误照常返回
+fn get_hash_field_ttls_internal<'a>(
+    conn: &mut redis::Connection,
+    key: &str,
+    fields: impl Iterator<Item = &'a str>,
+) -> Result<Option<FieldTtls>, String> {
+    let fields: Vec<&str> = fields.collect();
+    if fields.is_empty() {
+        return Ok(Some((Vec::new(), Vec::new())));
+    }
+
+    let mut pipe = redis::pipe();
+    pipe.cmd("HTTL").arg(key).arg("FIELDS").arg(fields.len()).arg(&fields);
+    pipe.cmd("HPEXPIRETIME").arg(key).arg("FIELDS").arg(fields.len()).arg(&fields);
+
+    match pipe.query::<FieldTtls>(conn) {
+        Ok(ttls) => Ok(Some(ttls)),
+        Err(e) if is_unknown_command(&e) => Ok(None),
+        Err(e) => Err(format!("获取字段过期时间失败: {}", e)),
+    }
+}
+
 // 获取键类型
 #[tauri::command]
 pub async fn get_key_type(
@@ -481,21 +975,140 @@ pub async fn get_key_type(
     }
 }
 
+// EXPIRE 系列命令的生效条件（Redis 7.0+）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ExpireCondition {
+    Nx, // 仅在键没有过期时间时设置
+    Xx, // 仅在键已有过期时间时设置
+    Gt, // 仅在新过期时间更晚时设置
+    Lt, // 仅在新过期时间更早时设置
+}
+
+impl ExpireCondition {
+    pub fn as_arg(self) -> &'static str {
+        match self {
+            ExpireCondition::Nx => "NX",
+            ExpireCondition::Xx => "XX",
+            ExpireCondition::Gt => "GT",
+            ExpireCondition::Lt => "LT",
+        }
+    }
+
+    // 按 Redis 的规则判断条件是否满足，-1 表示键没有过期时间（GT/LT 中视为无限长）
+    pub fn allows(self, current_pttl: i64, new_pttl: i64) -> bool {
+        match self {
+            ExpireCondition::Nx => current_pttl == -1,
+            ExpireCondition::Xx => current_pttl >= 0,
+            ExpireCondition::Gt => current_pttl >= 0 && new_pttl > current_pttl,
+            ExpireCondition::Lt => current_pttl == -1 || new_pttl < current_pttl,
+        }
+    }
+}
+
+// TTL 操作：相对毫秒数、绝对毫秒时间戳或移除过期时间
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum TtlOperation {
+    Expire { ttl_ms: i64 },
+    ExpireAt { timestamp_ms: i64 },
+    Persist,
+}
+
+impl TtlOperation {
+    pub fn validate(&self) -> Result<(), String> {
+        match self {
+            TtlOperation::Expire { ttl_ms } if *ttl_ms <= 0 => Err("TTL 必须大于 0".to_string()),
+            TtlOperation::ExpireAt { timestamp_ms } if *timestamp_ms <= 0 => Err("过期时间戳无效".to_string()),
+            _ => Ok(()),
+        }
+    }
+
+    // 向 pipeline 追加对应的 PEXPIRE / PEXPIREAT / PERSIST 命令，PERSIST 忽略条件
+    pub fn append(&self, pipe: &mut redis::Pipeline, key: &str, condition: Option<ExpireCondition>) {
+        match self {
+            TtlOperation::Expire { ttl_ms } => {
+                pipe.cmd("PEXPIRE").arg(key).arg(*ttl_ms);
+            }
+            TtlOperation::ExpireAt { timestamp_ms } => {
+                pipe.cmd("PEXPIREAT").arg(key).arg(*timestamp_ms);
+            }
+            TtlOperation::Persist => {
+                pipe.cmd("PERSIST").arg(key);
+                return;
+            }
+        }
+        if let Some(condition) = condition {
+            pipe.arg(condition.as_arg());
+        }
+    }
+
+    // 根据键当前的剩余毫秒数判断执行后是否会改变过期时间，用于试运行统计
+    pub fn would_change(&self, current_pttl: i64, condition: Option<ExpireCondition>, now_ms: i64) -> bool {
+        let new_pttl = match self {
+            TtlOperation::Persist => return current_pttl >= 0,
+            TtlOperation::Expire { ttl_ms } => *ttl_ms,
+            TtlOperation::ExpireAt { timestamp_ms } => timestamp_ms - now_ms,
+        };
+        if current_pttl == -2 {
+            return false;
+        }
+        match condition {
+            Some(condition) => condition.allows(current_pttl, new_pttl),
+            None => true,
+        }
+    }
+}
+
+// 键的过期时间
+#[derive(Debug, Serialize)]
+pub struct KeyTtl {
+    pub ttl: i64,       // 剩余秒数（按 Redis TTL 的规则四舍五入），-1 表示永久，-2 表示键不存在
+    pub pttl: i64,      // 剩余毫秒数
+    pub expire_at: i64, // 绝对过期毫秒时间戳，取值约定同上
+}
+
+// 读取键的过期时间，PEXPIRETIME 需要 Redis 7.0+，旧版本按当前时间加剩余毫秒数推算
+pub fn get_key_ttl_internal(conn: &mut redis::Connection, key: &str) -> Result<KeyTtl, String> {
+    let pttl: i64 = redis::cmd("PTTL")
+        .arg(key)
+        .query(conn)
+        .map_err(|e| format!("获取TTL失败: {}", e))?;
+
+    let expire_at = if pttl < 0 {
+        pttl
+    } else {
+        match redis::cmd("PEXPIRETIME").arg(key).query::<i64>(conn) {
+            Ok(expire_at) => expire_at,
+            Err(e) if is_unknown_command(&e) => Utc::now().timestamp_millis() + pttl,
+            Err(e) => return Err(format!("获取过期时间失败: {}", e)),
+        }
+    };
+
+    let ttl = if pttl < 0 { pttl } else { (pttl + 500) / 1000 };
+    Ok(KeyTtl { ttl, pttl, expire_at })
+}
+
 // 获取键 TTL
 #[tauri::command]
 pub async fn get_key_ttl(
     connection_id: String,
     key: String,
     state: State<'_, AppState>,
-) -> Result<Response<i64>, String> {
+) -> Result<Response<KeyTtl>, String> {
     let connections = state.connections.lock().unwrap();
 
     if let Some(conn_state) = connections.get(&connection_id) {
         match conn_state.client.get_connection() {
             Ok(mut conn) => {
-                match redis::cmd("TTL").arg(&key).query::<i64>(&mut conn) {
+                let current_db = conn_state.current_db;
+                if let Err(e) = redis::cmd("SELECT").arg(current_db).query::<()>(&mut conn) {
+                    return Ok(Response::error(format!("切换到数据库 {} 失败: {}", current_db, e)));
+                }
+
+                match get_key_ttl_internal(&mut conn, &key) {
                     Ok(ttl) => Ok(Response::success(ttl)),
-                    Err(e) => Ok(Response::error(format!("获取键 TTL 失败: {}", e))),
+                    Err(e) => Ok(Response::error(e)),
                 }
             }
             Err(e) => Ok(Response::error(format!("获取连接失败: {}", e))),
@@ -505,41 +1118,49 @@ pub async fn get_key_ttl(
     }
 }
 
-// 设置键 TTL
+// 设置键 TTL：相对毫秒数（PEXPIRE）、绝对毫秒时间戳（PEXPIREAT）或移除过期时间（PERSIST）
+// condition 对应 NX/XX/GT/LT，成功后返回新的过期时间
 #[tauri::command]
 pub async fn set_key_ttl(
     connection_id: String,
     key: String,
-    ttl: i64,
+    operation: TtlOperation,
+    condition: Option<ExpireCondition>,
     state: State<'_, AppState>,
-) -> Result<Response<()>, String> {
+) -> Result<Response<KeyTtl>, String> {
+    if let Err(e) = operation.validate() {
+        return Ok(Response::error(e));
+    }
+
     let connections = state.connections.lock().unwrap();
 
     if let Some(conn_state) = connections.get(&connection_id) {
         match conn_state.client.get_connection() {
             Ok(mut conn) => {
-                if ttl > 0 {
-                    match redis::cmd("EXPIRE").arg(&key).arg(ttl).query::<i64>(&mut conn) {
-                        Ok(result) => {
-                            if result == 1 {
-                                Ok(Response::<()>::success_empty())
-                            } else {
-                                Ok(Response::error("设置TTL失败".to_string()))
-                            }
-                        }
-                        Err(e) => Ok(Response::error(format!("设置 TTL 失败: {}", e))),
-                    }
-                } else {
-                    match redis::cmd("PERSIST").arg(&key).query::<i64>(&mut conn) {
-                        Ok(result) => {
-                            if result == 1 {
-                                Ok(Response::<()>::success_empty())
-                            } else {
-                                Ok(Response::error("移除 TTL 失败".to_string()))
-                            }
-                        }
-                        Err(e) => Ok(Response::error(format!("移除 TTL 失败: {}", e))),
-                    }
+                let current_db = conn_state.current_db;
+                if let Err(e) = redis::cmd("SELECT").arg(current_db).query::<()>(&mut conn) {
+                    return Ok(Response::error(format!("切换到数据库 {} 失败: {}", current_db, e)));
+                }
+
+                let mut pipe = redis::pipe();
+                operation.append(&mut pipe, &key, condition);
+                let changed = match pipe.query::<(i64,)>(&mut conn) {
+                    Ok((changed,)) => changed == 1,
+                    Err(e) => return Ok(Response::error(format!("设置 TTL 失败: {}", e))),
+                };
+
+                if !changed {
+                    let reason = match operation {
+                        TtlOperation::Persist => "键不存在或没有过期时间",
+                        _ => "键不存在或不满足条件",
+                    };
+                    return Ok(Response::error(format!("未修改 {} 的过期时间: {}", key, reason)));
+                }
+
+                match get_key_ttl_internal(&mut conn, &key) {
+                    // 过期时间早于当前时间时键会被立即删除，此时返回 -2
+                    Ok(ttl) => Ok(Response::success_with_message(ttl, format!("已更新 {} 的过期时间", key))),
+                    Err(e) => Ok(Response::error(e)),
                 }
             }
             Err(e) => Ok(Response::error(format!("获取连接失败: {}", e))),
@@ -610,20 +1231,41 @@ pub async fn rename_key(
     connection_id: String,
     old_key: String,
     new_key: String,
+    overwrite: Option<bool>, // 为 false 时使用 RENAMENX，目标键已存在则不重命名；默认覆盖
     state: State<'_, AppState>,
 ) -> Result<Response<()>, String> {
+    let overwrite = overwrite.unwrap_or(true);
     let connections = state.connections.lock().unwrap();
 
     if let Some(conn_state) = connections.get(&connection_id) {
         match conn_state.client.get_connection() {
             Ok(mut conn) => {
-                match redis::cmd("RENAME")
-                    .arg(&old_key)
-                    .arg(&new_key)
-                    .query::<()>(&mut conn)
-                {
-                    Ok(_) => Ok(Response::<()>::success_empty_with_message(format!("成功重命名 {} 为 {}", old_key, new_key))),
-                    Err(e) => Ok(Response::error(format!("重命名失败: {}", e))),
+                let current_db = conn_state.current_db;
+                if let Err(e) = redis::cmd("SELECT").arg(current_db).query::<()>(&mut conn) {
+                    return Ok(Response::error(format!("切换到数据库 {} 失败: {}", current_db, e)));
+                }
+
+                let client = &conn_state.client;
+                let command = if overwrite { "RENAME" } else { "RENAMENX" };
+                let result = match query_routed::<redis::Value>(
+                    client,
+                    &mut conn,
+                    redis::cmd(command).arg(&old_key).arg(&new_key),
+                ) {
+                    // RENAMENX 返回 0 表示目标键已存在
+                    Ok(redis::Value::Int(0)) => Ok(false),
+                    Ok(_) => Ok(true),
+                    // 集群模式下源键和目标键不在同一个槽位时，改为 DUMP/RESTORE 后删除源键
+                    Err(e) if e.code() == Some("CROSSSLOT") => {
+                        rename_across_slots(client, &mut conn, &old_key, &new_key, overwrite)
+                    }
+                    Err(e) => Err(format!("重命名失败: {}", e)),
+                };
+
+                match result {
+                    Ok(true) => Ok(Response::<()>::success_empty_with_message(format!("成功重命名 {} 为 {}", old_key, new_key))),
+                    Ok(false) => Ok(Response::error(format!("目标键 {} 已存在，未重命名", new_key))),
+                    Err(e) => Ok(Response::error(e)),
                 }
             }
             Err(e) => Ok(Response::error(format!("获取连接失败: {}", e))),
@@ -633,15 +1275,94 @@ pub async fn rename_key(
     }
 }
 
-// 新增命令：更新哈希字段
-#[tauri::command]
-pub async fn update_hash_field(
-    connection_id: String,
-    key: String,
-    field: String,
+// 连接到集群重定向指向的节点，沿用原连接的认证信息和 TLS 设置
+fn redirect_connection(client: &redis::Client, addr: &str) -> redis::RedisResult<redis::Connection> {
+    let (host, port) = addr
+        .rsplit_once(':')
+        .and_then(|(host, port)| Some((host.to_string(), port.parse::<u16>().ok()?)))
+        .ok_or_else(|| {
+            redis::RedisError::from((redis::ErrorKind::ClientError, "无效的重定向地址", addr.to_string()))
+        })?;
+
+    let mut info = client.get_connection_info().clone();
+    info.addr = match info.addr {
+        redis::ConnectionAddr::TcpTls { insecure, tls_params, .. } => redis::ConnectionAddr::TcpTls {
+            host,
+            port,
+            insecure,
+            tls_params,
+        },
+        _ => redis::ConnectionAddr::Tcp(host, port),
+    };
+    redis::Client::open(info)?.get_connection()
+}
+
+// 在负责键所在槽位的节点上执行单键命令：收到 MOVED / ASK 重定向时连接到对应节点重试
+// 非集群模式下与直接执行相同
+fn query_routed<T: redis::FromRedisValue>(
+    client: &redis::Client,
+    conn: &mut redis::Connection,
+    cmd: &redis::Cmd,
+) -> redis::RedisResult<T> {
+    match cmd.query(conn) {
+        Err(e) => match e.redirect_node() {
+            Some((addr, _)) => {
+                let mut node = redirect_connection(client, addr)?;
+                if e.kind() == redis::ErrorKind::Ask {
+                    redis::cmd("ASKING").query::<()>(&mut node)?;
+                }
+                cmd.query(&mut node)
+            }
+            None => Err(e),
+        },
+        result => result,
+    }
+}
+
+// 跨槽位重命名：在源键所在节点 DUMP 并读取 PTTL，在目标键所在节点 RESTORE（保留 TTL），成功后删除源键
+// 返回 false 表示不覆盖时目标键已存在，与 RENAMENX 一致
+fn rename_across_slots(
+    client: &redis::Client,
+    conn: &mut redis::Connection,
+    old_key: &str,
+    new_key: &str,
+    overwrite: bool,
+) -> Result<bool, String> {
+    let payload: Option<Vec<u8>> = query_routed(client, conn, redis::cmd("DUMP").arg(old_key))
+        .map_err(|e| format!("DUMP 失败: {}", e))?;
+    let pttl: i64 = query_routed(client, conn, redis::cmd("PTTL").arg(old_key))
+        .map_err(|e| format!("获取TTL失败: {}", e))?;
+    let (Some(payload), false) = (payload, pttl == -2) else {
+        return Err(format!("重命名失败: 键 {} 不存在", old_key));
+    };
+
+    // RESTORE 的 TTL 为 0 表示永不过期
+    let mut restore = redis::cmd("RESTORE");
+    restore.arg(new_key).arg(pttl.max(0)).arg(payload);
+    if overwrite {
+        restore.arg("REPLACE");
+    }
+    match query_routed::<()>(client, conn, &restore) {
+        Ok(_) => {}
+        Err(e) if e.code() == Some("BUSYKEY") => return Ok(false),
+        Err(e) => return Err(format!("RESTORE 失败: {}", e)),
+    }
+
+    query_routed::<()>(client, conn, redis::cmd("DEL").arg(old_key))
+        .map_err(|e| format!("已复制到 {}，但删除源键失败: {}", new_key, e))?;
+    Ok(true)
+}
+
+// 新增命令：更新哈希字段
+#[tauri::command]
+pub async fn update_hash_field(
+    connection_id: String,
+    key: String,
+    field: String,
     value: String,
+    version: Option<String>, // get_key_detail 返回的版本号，传入时检测并发修改
     state: State<'_, AppState>,
-) -> Result<Response<()>, String> {
+) -> Result<Response<EditResult>, String> {
     let connections = state.connections.lock().unwrap();
 
     if let Some(conn_state) = connections.get(&connection_id) {
@@ -652,13 +1373,39 @@ pub async fn update_hash_field(
                     return Ok(Response::error(format!("切换到数据库 {} 失败: {}", current_db, e)));
                 }
 
-                match redis::cmd("HSET")
-                    .arg(&key)
-                    .arg(&field)
-                    .arg(&value)
-                    .query::<i64>(&mut conn)
-                {
-                    Ok(_) => Ok(Response::<()>::success_empty_with_message(format!("成功更新 {} 的字段 {}", key, field))),
+                // 键名匹配 Protobuf 映射时，value 为 JSON 文本，编码后保存
+                let bytes = match conn_state.proto.message_type_for_key(&key) {
+                    Some(message_type) => match conn_state.proto.encode(message_type, &value) {
+                        Ok(bytes) => bytes,
+                        Err(e) => return Ok(Response::error(e)),
+                    },
+                    None => value.into_bytes(),
+                };
+
+                // HSET 会清除字段原有的过期时间，写入后按原来的绝对过期时间重新设置
+                let expire_at = match get_hash_field_ttls_internal(&mut conn, &key, std::iter::once(field.as_str())) {
+                    Ok(Some((_, expire_ats))) => expire_ats.first().copied().filter(|at| *at > 0),
+                    Ok(None) => None,
+                    Err(e) => return Ok(Response::error(e)),
+                };
+
+                let mut pipe = redis::pipe();
+                pipe.cmd("HSET").arg(&key).arg(&field).arg(&bytes).ignore();
+                if let Some(expire_at) = expire_at {
+                    pipe.cmd("HPEXPIREAT").arg(&key).arg(expire_at).arg("FIELDS").arg(1).arg(&field).ignore();
+                }
+
+                match write_with_version(&mut conn, &key, version.as_deref(), WriteMode::Any, &mut pipe) {
+                    Ok(WriteOutcome::Written) => Ok(Response::success_with_message(
+                        EditResult::written(&mut conn, &key),
+                        format!("成功更新 {} 的字段 {}", key, field),
+                    )),
+                    Ok(WriteOutcome::Rejected(reason)) => Ok(Response::error(reason)),
+                    Ok(WriteOutcome::Conflict) => {
+                        let current: Option<Vec<u8>> = redis::cmd("HGET").arg(&key).arg(&field).query(&mut conn).unwrap_or_default();
+                        let current = current.map(|v| json!(String::from_utf8_lossy(&v))).unwrap_or(serde_json::Value::Null);
+                        Ok(EditResult::conflict(&mut conn, &key, current))
+                    }
                     Err(e) => Ok(Response::error(format!("更新失败: {}", e))),
                 }
             }
@@ -709,6 +1456,131 @@ pub async fn delete_hash_field(
     }
 }
 
+// 新增命令：获取哈希字段过期时间
+// 返回 [ttl 秒数, expire_at 毫秒时间戳] 列表，服务端不支持时返回错误
+#[tauri::command]
+pub async fn get_hash_field_ttls(
+    connection_id: String,
+    key: String,
+    fields: Vec<String>,
+    state: State<'_, AppState>,
+) -> Result<Response<Vec<(i64, i64)>>, String> {
+    let connections = state.connections.lock().unwrap();
+
+    if let Some(conn_state) = connections.get(&connection_id) {
+        match conn_state.client.get_connection() {
+            Ok(mut conn) => {
+                let current_db = conn_state.current_db;
+                if let Err(e) = redis::cmd("SELECT").arg(current_db).query::<()>(&mut conn) {
+                    return Ok(Response::error(format!("切换到数据库 {} 失败: {}", current_db, e)));
+                }
+
+                match get_hash_field_ttls_internal(&mut conn, &key, fields.iter().map(|f| f.as_str())) {
+                    Ok(Some((ttls, expire_ats))) => Ok(Response::success(ttls.into_iter().zip(expire_ats).collect())),
+                    Ok(None) => Ok(Response::error("当前服务器不支持哈希字段过期（需要 Redis 7.4+ 或 Valkey 9+）".to_string())),
+                    Err(e) => Ok(Response::error(e)),
+                }
+            }
+            Err(e) => Ok(Response::error(format!("获取连接失败: {}", e))),
+        }
+    } else {
+        Ok(Response::error("Redis 未连接".to_string()))
+    }
+}
+
+// 新增命令：设置哈希字段过期时间（HEXPIRE / HPEXPIRE）
+// 返回每个字段的结果：1 设置成功，0 条件不满足，2 过期时间为 0 字段已被删除，-2 字段不存在
+#[tauri::command]
+pub async fn expire_hash_fields(
+    connection_id: String,
+    key: String,
+    fields: Vec<String>,
+    ttl: i64,
+    milliseconds: bool,
+    condition: Option<String>, // "nx" / "xx" / "gt" / "lt"
+    state: State<'_, AppState>,
+) -> Result<Response<Vec<i64>>, String> {
+    if fields.is_empty() {
+        return Ok(Response::error("至少需要一个字段".to_string()));
+    }
+
+    let mut cmd = redis::cmd(if milliseconds { "HPEXPIRE" } else { "HEXPIRE" });
+    cmd.arg(&key).arg(ttl);
+    if let Some(condition) = &condition {
+        match condition.to_lowercase().as_str() {
+            "nx" | "xx" | "gt" | "lt" => {
+                cmd.arg(condition.to_uppercase());
+            }
+            _ => return Ok(Response::error(format!("不支持的条件: {}", condition))),
+        }
+    }
+    cmd.arg("FIELDS").arg(fields.len()).arg(&fields);
+
+    let connections = state.connections.lock().unwrap();
+
+    if let Some(conn_state) = connections.get(&connection_id) {
+        match conn_state.client.get_connection() {
+            Ok(mut conn) => {
+                let current_db = conn_state.current_db;
+                if let Err(e) = redis::cmd("SELECT").arg(current_db).query::<()>(&mut conn) {
+                    return Ok(Response::error(format!("切换到数据库 {} 失败: {}", current_db, e)));
+                }
+
+                match cmd.query::<Vec<i64>>(&mut conn) {
+                    Ok(results) => Ok(Response::success_with_message(results, format!("成功设置 {} 的字段过期时间", key))),
+                    Err(e) if is_unknown_command(&e) => Ok(Response::error("当前服务器不支持哈希字段过期（需要 Redis 7.4+ 或 Valkey 9+）".to_string())),
+                    Err(e) => Ok(Response::error(format!("设置字段过期时间失败: {}", e))),
+                }
+            }
+            Err(e) => Ok(Response::error(format!("获取连接失败: {}", e))),
+        }
+    } else {
+        Ok(Response::error("Redis 未连接".to_string()))
+    }
+}
+
+// 新增命令：移除哈希字段过期时间（HPERSIST）
+// 返回每个字段的结果：1 移除成功，-1 字段没有过期时间，-2 字段不存在
+#[tauri::command]
+pub async fn persist_hash_fields(
+    connection_id: String,
+    key: String,
+    fields: Vec<String>,
+    state: State<'_, AppState>,
+) -> Result<Response<Vec<i64>>, String> {
+    if fields.is_empty() {
+        return Ok(Response::error("至少需要一个字段".to_string()));
+    }
+
+    let connections = state.connections.lock().unwrap();
+
+    if let Some(conn_state) = connections.get(&connection_id) {
+        match conn_state.client.get_connection() {
+            Ok(mut conn) => {
+                let current_db = conn_state.current_db;
+                if let Err(e) = redis::cmd("SELECT").arg(current_db).query::<()>(&mut conn) {
+                    return Ok(Response::error(format!("切换到数据库 {} 失败: {}", current_db, e)));
+                }
+
+                match redis::cmd("HPERSIST")
+                    .arg(&key)
+                    .arg("FIELDS")
+                    .arg(fields.len())
+                    .arg(&fields)
+                    .query::<Vec<i64>>(&mut conn)
+                {
+                    Ok(results) => Ok(Response::success_with_message(results, format!("成功移除 {} 的字段过期时间", key))),
+                    Err(e) if is_unknown_command(&e) => Ok(Response::error("当前服务器不支持哈希字段过期（需要 Redis 7.4+ 或 Valkey 9+）".to_string())),
+                    Err(e) => Ok(Response::error(format!("移除字段过期时间失败: {}", e))),
+                }
+            }
+            Err(e) => Ok(Response::error(format!("获取连接失败: {}", e))),
+        }
+    } else {
+        Ok(Response::error("Redis 未连接".to_string()))
+    }
+}
+
 // 新增命令：更新列表项
 #[tauri::command]
 pub async fn update_list_item(
@@ -716,8 +1588,9 @@ pub async fn update_list_item(
     key: String,
     index: i64,
     value: String,
+    version: Option<String>, // get_key_detail 返回的版本号，传入时检测并发修改
     state: State<'_, AppState>,
-) -> Result<Response<()>, String> {
+) -> Result<Response<EditResult>, String> {
     let connections = state.connections.lock().unwrap();
 
     if let Some(conn_state) = connections.get(&connection_id) {
@@ -749,13 +1622,19 @@ pub async fn update_list_item(
                     index
                 };
 
-                match redis::cmd("LSET")
-                    .arg(&key)
-                    .arg(effective_index)
-                    .arg(&value)
-                    .query::<()>(&mut conn)
-                {
-                    Ok(_) => Ok(Response::<()>::success_empty_with_message(format!("成功更新 {} 的索引 {}", key, index))),
+                let mut pipe = redis::pipe();
+                pipe.cmd("LSET").arg(&key).arg(effective_index).arg(&value).ignore();
+
+                match write_with_version(&mut conn, &key, version.as_deref(), WriteMode::Any, &mut pipe) {
+                    Ok(WriteOutcome::Written) => Ok(Response::success_with_message(
+                        EditResult::written(&mut conn, &key),
+                        format!("成功更新 {} 的索引 {}", key, index),
+                    )),
+                    Ok(WriteOutcome::Rejected(reason)) => Ok(Response::error(reason)),
+                    Ok(WriteOutcome::Conflict) => {
+                        let current = read_current_value(&mut conn, &key);
+                        Ok(EditResult::conflict(&mut conn, &key, current))
+                    }
                     Err(e) => Ok(Response::error(format!("更新失败: {}", e))),
                 }
             }
@@ -842,6 +1721,279 @@ pub async fn append_list_item(
     }
 }
 
+// 新增命令：在列表头部添加元素
+#[tauri::command]
+pub async fn prepend_list_item(
+    connection_id: String,
+    key: String,
+    value: String,
+    state: State<'_, AppState>,
+) -> Result<Response<()>, String> {
+    let connections = state.connections.lock().unwrap();
+
+    if let Some(conn_state) = connections.get(&connection_id) {
+        match conn_state.client.get_connection() {
+            Ok(mut conn) => {
+                let current_db = conn_state.current_db;
+                if let Err(e) = redis::cmd("SELECT").arg(current_db).query::<()>(&mut conn) {
+                    return Ok(Response::error(format!("切换到数据库 {} 失败: {}", current_db, e)));
+                }
+
+                match redis::cmd("LPUSH")
+                    .arg(&key)
+                    .arg(&value)
+                    .query::<i64>(&mut conn)
+                {
+                    Ok(new_len) => Ok(Response::<()>::success_empty_with_message(format!("成功在列表 {} 头部添加元素，新长度: {}", key, new_len))),
+                    Err(e) => Ok(Response::error(format!("添加失败: {}", e))),
+                }
+            }
+            Err(e) => Ok(Response::error(format!("获取连接失败: {}", e))),
+        }
+    } else {
+        Ok(Response::error("Redis 未连接".to_string()))
+    }
+}
+
+// 新增命令：在指定元素前/后插入元素
+#[tauri::command]
+pub async fn insert_list_item(
+    connection_id: String,
+    key: String,
+    pivot: String,
+    value: String,
+    position: String, // "before" 或 "after"
+    state: State<'_, AppState>,
+) -> Result<Response<()>, String> {
+    let where_arg = match position.to_lowercase().as_str() {
+        "before" => "BEFORE",
+        "after" => "AFTER",
+        _ => return Ok(Response::error(format!("不支持的插入位置: {}", position))),
+    };
+
+    let connections = state.connections.lock().unwrap();
+
+    if let Some(conn_state) = connections.get(&connection_id) {
+        match conn_state.client.get_connection() {
+            Ok(mut conn) => {
+                let current_db = conn_state.current_db;
+                if let Err(e) = redis::cmd("SELECT").arg(current_db).query::<()>(&mut conn) {
+                    return Ok(Response::error(format!("切换到数据库 {} 失败: {}", current_db, e)));
+                }
+
+                // LINSERT 返回新长度，-1 表示未找到基准元素，0 表示键不存在
+                match redis::cmd("LINSERT")
+                    .arg(&key)
+                    .arg(where_arg)
+                    .arg(&pivot)
+                    .arg(&value)
+                    .query::<i64>(&mut conn)
+                {
+                    Ok(-1) => Ok(Response::error(format!("未找到基准元素 {}", pivot))),
+                    Ok(0) => Ok(Response::error(format!("列表 {} 不存在", key))),
+                    Ok(new_len) => Ok(Response::<()>::success_empty_with_message(format!("成功插入元素，新长度: {}", new_len))),
+                    Err(e) => Ok(Response::error(format!("插入失败: {}", e))),
+                }
+            }
+            Err(e) => Ok(Response::error(format!("获取连接失败: {}", e))),
+        }
+    } else {
+        Ok(Response::error("Redis 未连接".to_string()))
+    }
+}
+
+// 新增命令：从列表头部或尾部弹出元素
+#[tauri::command]
+pub async fn pop_list_items(
+    connection_id: String,
+    key: String,
+    side: String, // "left" 或 "right"
+    count: usize,
+    state: State<'_, AppState>,
+) -> Result<Response<Vec<String>>, String> {
+    let pop_cmd = match side.to_lowercase().as_str() {
+        "left" => "LPOP",
+        "right" => "RPOP",
+        _ => return Ok(Response::error(format!("不支持的弹出方向: {}", side))),
+    };
+
+    if count == 0 {
+        return Ok(Response::error("弹出数量必须大于 0".to_string()));
+    }
+
+    let connections = state.connections.lock().unwrap();
+
+    if let Some(conn_state) = connections.get(&connection_id) {
+        match conn_state.client.get_connection() {
+            Ok(mut conn) => {
+                let current_db = conn_state.current_db;
+                if let Err(e) = redis::cmd("SELECT").arg(current_db).query::<()>(&mut conn) {
+                    return Ok(Response::error(format!("切换到数据库 {} 失败: {}", current_db, e)));
+                }
+
+                // 带 count 参数时返回数组，列表不存在时返回 nil
+                match redis::cmd(pop_cmd)
+                    .arg(&key)
+                    .arg(count)
+                    .query::<Option<Vec<String>>>(&mut conn)
+                {
+                    Ok(Some(items)) => {
+                        let message = format!("成功弹出 {} 个元素", items.len());
+                        Ok(Response::success_with_message(items, message))
+                    }
+                    Ok(None) => Ok(Response::error(format!("列表 {} 为空或不存在", key))),
+                    Err(e) => Ok(Response::error(format!("弹出失败: {}", e))),
+                }
+            }
+            Err(e) => Ok(Response::error(format!("获取连接失败: {}", e))),
+        }
+    } else {
+        Ok(Response::error("Redis 未连接".to_string()))
+    }
+}
+
+// 新增命令：裁剪列表，只保留指定区间内的元素
+#[tauri::command]
+pub async fn trim_list(
+    connection_id: String,
+    key: String,
+    start: i64,
+    stop: i64,
+    state: State<'_, AppState>,
+) -> Result<Response<()>, String> {
+    let connections = state.connections.lock().unwrap();
+
+    if let Some(conn_state) = connections.get(&connection_id) {
+        match conn_state.client.get_connection() {
+            Ok(mut conn) => {
+                let current_db = conn_state.current_db;
+                if let Err(e) = redis::cmd("SELECT").arg(current_db).query::<()>(&mut conn) {
+                    return Ok(Response::error(format!("切换到数据库 {} 失败: {}", current_db, e)));
+                }
+
+                match redis::cmd("LTRIM")
+                    .arg(&key)
+                    .arg(start)
+                    .arg(stop)
+                    .query::<()>(&mut conn)
+                {
+                    Ok(_) => Ok(Response::<()>::success_empty_with_message(format!("成功裁剪列表 {} 到区间 [{}, {}]", key, start, stop))),
+                    Err(e) => Ok(Response::error(format!("裁剪失败: {}", e))),
+                }
+            }
+            Err(e) => Ok(Response::error(format!("获取连接失败: {}", e))),
+        }
+    } else {
+        Ok(Response::error("Redis 未连接".to_string()))
+    }
+}
+
+// 新增命令：在两个列表之间移动元素
+#[tauri::command]
+pub async fn move_list_item(
+    connection_id: String,
+    source: String,
+    destination: String,
+    from_side: String, // "left" 或 "right"
+    to_side: String,   // "left" 或 "right"
+    state: State<'_, AppState>,
+) -> Result<Response<String>, String> {
+    let parse_side = |side: &str| match side.to_lowercase().as_str() {
+        "left" => Some("LEFT"),
+        "right" => Some("RIGHT"),
+        _ => None,
+    };
+    let (where_from, where_to) = match (parse_side(&from_side), parse_side(&to_side)) {
+        (Some(f), Some(t)) => (f, t),
+        _ => return Ok(Response::error(format!("不支持的移动方向: {} -> {}", from_side, to_side))),
+    };
+
+    let connections = state.connections.lock().unwrap();
+
+    if let Some(conn_state) = connections.get(&connection_id) {
+        match conn_state.client.get_connection() {
+            Ok(mut conn) => {
+                let current_db = conn_state.current_db;
+                if let Err(e) = redis::cmd("SELECT").arg(current_db).query::<()>(&mut conn) {
+                    return Ok(Response::error(format!("切换到数据库 {} 失败: {}", current_db, e)));
+                }
+
+                match redis::cmd("LMOVE")
+                    .arg(&source)
+                    .arg(&destination)
+                    .arg(where_from)
+                    .arg(where_to)
+                    .query::<Option<String>>(&mut conn)
+                {
+                    Ok(Some(item)) => Ok(Response::success_with_message(item, format!("成功从 {} 移动元素到 {}", source, destination))),
+                    Ok(None) => Ok(Response::error(format!("源列表 {} 为空或不存在", source))),
+                    Err(e) => Ok(Response::error(format!("移动失败: {}", e))),
+                }
+            }
+            Err(e) => Ok(Response::error(format!("获取连接失败: {}", e))),
+        }
+    } else {
+        Ok(Response::error("Redis 未连接".to_string()))
+    }
+}
+
+// 新增命令：按索引删除单个列表项
+// LREM 只能按值删除且会删除所有重复项，这里先用 LSET 把目标位置替换为唯一的占位值，
+// 再用 LREM count=1 删除该占位值，两步放在同一个 MULTI 事务中执行
+#[tauri::command]
+pub async fn delete_list_item_by_index(
+    connection_id: String,
+    key: String,
+    index: i64,
+    state: State<'_, AppState>,
+) -> Result<Response<()>, String> {
+    let connections = state.connections.lock().unwrap();
+
+    if let Some(conn_state) = connections.get(&connection_id) {
+        match conn_state.client.get_connection() {
+            Ok(mut conn) => {
+                let current_db = conn_state.current_db;
+                if let Err(e) = redis::cmd("SELECT").arg(current_db).query::<()>(&mut conn) {
+                    return Ok(Response::error(format!("切换到数据库 {} 失败: {}", current_db, e)));
+                }
+
+                // 获取列表长度
+                let len: i64 = match redis::cmd("LLEN").arg(&key).query(&mut conn) {
+                    Ok(len) => len,
+                    Err(e) => {
+                        return Ok(Response::error(format!("获取列表长度失败: {}", e)))
+                    }
+                };
+
+                // 验证索引是否在有效范围内
+                if index >= len || index < -len {
+                    return Ok(Response::error(format!("索引 {} 超出范围，列表长度为 {}", index, len)));
+                }
+
+                let tombstone = format!(
+                    "__byte_redis_tombstone__:{}",
+                    Utc::now().timestamp_nanos_opt().unwrap_or_default()
+                );
+
+                let result = redis::pipe()
+                    .atomic()
+                    .cmd("LSET").arg(&key).arg(index).arg(&tombstone).ignore()
+                    .cmd("LREM").arg(&key).arg(1).arg(&tombstone)
+                    .query::<(i64,)>(&mut conn);
+
+                match result {
+                    Ok((1,)) => Ok(Response::<()>::success_empty_with_message(format!("成功删除 {} 的索引 {}", key, index))),
+                    Ok(_) => Ok(Response::error("删除失败: 未找到占位元素".to_string())),
+                    Err(e) => Ok(Response::error(format!("删除失败: {}", e))),
+                }
+            }
+            Err(e) => Ok(Response::error(format!("获取连接失败: {}", e))),
+        }
+    } else {
+        Ok(Response::error("Redis 未连接".to_string()))
+    }
+}
+
 // 新增命令：添加Set元素
 #[tauri::command]
 pub async fn add_set_item(
@@ -1003,3 +2155,31 @@ pub async fn delete_zset_item(
         Ok(Response::error("Redis 未连接".to_string()))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn slot_tag_uses_hash_tag_or_whole_key() {
+        assert_eq!(slot_tag("user:{42}:profile"), Some("42"));
+        assert_eq!(slot_tag("{a}{b}"), Some("a"));
+        assert_eq!(slot_tag("user:42"), Some("user:42"));
+        assert_eq!(slot_tag("a{b"), Some("a{b"));
+        assert_eq!(slot_tag("a{}b"), None);
+        assert_eq!(slot_tag("a}b"), None);
+    }
+
+    #[test]
+    fn temp_key_shares_slot_tag_with_target() {
+        let temp = temp_key_for("replace", "user:{42}:profile");
+        assert!(temp.starts_with("__byte_redis_replace__:{42}:"));
+        assert_eq!(slot_tag(&temp), Some("42"));
+
+        let temp = temp_key_for("replace", "订单:1");
+        assert_eq!(slot_tag(&temp), Some("订单:1"));
+
+        let temp = temp_key_for("replace", "a{b");
+        assert_eq!(slot_tag(&temp), Some("a{b"));
+    }
+}
@@ -0,0 +1,332 @@
+use crate::state::AppState;
+use crate::commands::response::Response;
+use serde::Serialize;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, State};
+
+// 键空间通知事件名
+pub const KEYSPACE_EVENT: &str = "keyspace-event";
+// 订阅因错误中断时推送的事件名
+pub const KEYSPACE_CLOSED_EVENT: &str = "keyspace-closed";
+
+// 连接上的键空间通知订阅，释放时通知后台线程退出
+// 需要等待线程退出或恢复 notify-keyspace-events 时调用 shutdown
+pub struct KeyspaceSubscription {
+    stop: Arc<AtomicBool>,
+    dead: Arc<AtomicBool>, // 后台线程因错误退出
+    listener: Option<JoinHandle<()>>,
+    restore: Option<(redis::Client, String)>, // 订阅前的 notify-keyspace-events，未修改时为空
+}
+
+impl KeyspaceSubscription {
+    // 后台线程是否已因错误退出
+    pub fn is_dead(&self) -> bool {
+        self.dead.load(Ordering::Relaxed)
+    }
+
+    // 停止后台线程并等待它关闭订阅连接，然后恢复订阅时修改的 notify-keyspace-events
+    // 最多等待一个读取超时并访问服务端，调用方应先释放连接表的锁
+    pub fn shutdown(mut self) -> Result<(), String> {
+        self.stop.store(true, Ordering::Relaxed);
+        if let Some(listener) = self.listener.take() {
+            let _ = listener.join();
+        }
+        match self.restore.take() {
+            Some((client, original)) => client
+                .get_connection()
+                .and_then(|mut conn| {
+                    redis::cmd("CONFIG")
+                        .arg("SET")
+                        .arg("notify-keyspace-events")
+                        .arg(&original)
+                        .query::<()>(&mut conn)
+                })
+                .map_err(|e| format!("恢复 notify-keyspace-events 失败: {}", e)),
+            None => Ok(()),
+        }
+    }
+}
+
+impl Drop for KeyspaceSubscription {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+    }
+}
+
+// 推送给前端的键变化事件
+#[derive(Debug, Clone, Serialize)]
+pub struct KeyspaceEvent {
+    pub connection_id: String,
+    pub db: u8,
+    pub key: String,
+    pub event: String, // 例如 set、del、expire、expired、evicted、hset、lpush
+}
+
+// 订阅因错误中断的通知
+#[derive(Debug, Clone, Serialize)]
+pub struct KeyspaceClosed {
+    pub connection_id: String,
+    pub error: String,
+}
+
+// 订阅结果
+#[derive(Debug, Serialize)]
+pub struct KeyspaceSubscriptionInfo {
+    pub db: u8,
+    pub patterns: Vec<String>,
+    pub notify_keyspace_events: String, // 服务端当前的 notify-keyspace-events 配置
+    pub enabled_notifications: bool,    // 本次订阅是否修改了配置，取消订阅或断开连接时恢复
+}
+
+// 转义键名中的 glob 特殊字符，使 PSUBSCRIBE 只匹配该键
+fn escape_glob(key: &str) -> String {
+    let mut escaped = String::with_capacity(key.len());
+    for c in key.chars() {
+        if matches!(c, '*' | '?' | '[' | ']' | '\\') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+fn read_keyspace_events(conn: &mut redis::Connection) -> Result<String, String> {
+    let config: Vec<String> = redis::cmd("CONFIG")
+        .arg("GET")
+        .arg("notify-keyspace-events")
+        .query(conn)
+        .map_err(|e| format!("读取 notify-keyspace-events 失败: {}", e))?;
+    Ok(config.get(1).cloned().unwrap_or_default())
+}
+
+fn keyspace_events_enabled(flags: &str) -> bool {
+    flags.contains('K') && flags.contains('A')
+}
+
+// 开启键空间通知（K 加上全部事件类型 A），这是服务端全局配置，会影响所有客户端
+// 返回修改后的配置；已开启时不做修改，返回 None
+fn enable_keyspace_events(conn: &mut redis::Connection, current: &str) -> Result<Option<String>, String> {
+    if keyspace_events_enabled(current) {
+        return Ok(None);
+    }
+
+    // 保留原有的 E（键事件）等标记，补上 K 和 A
+    let mut flags: String = current.chars().filter(|c| *c != 'K' && *c != 'A').collect();
+    flags.push_str("KA");
+    redis::cmd("CONFIG")
+        .arg("SET")
+        .arg("notify-keyspace-events")
+        .arg(&flags)
+        .query::<()>(conn)
+        .map_err(|e| format!("开启 notify-keyspace-events 失败: {}", e))?;
+    Ok(Some(flags))
+}
+
+// 订阅键空间通知：传入 keys 时只订阅这些键，传入 pattern 时订阅匹配的键，都为空时订阅当前库的全部键
+// 每个连接只保留一个订阅，重复调用会替换之前的订阅；切换数据库后需要重新订阅
+// enable_notifications 为 true 时，服务端未开启键空间通知则通过 CONFIG SET 开启（全局生效），
+// 取消订阅或断开连接时恢复原来的配置；为 false 时不修改服务端配置
+#[tauri::command]
+pub async fn subscribe_keyspace(
+    app: AppHandle,
+    connection_id: String,
+    keys: Option<Vec<String>>,
+    pattern: Option<String>,
+    enable_notifications: bool,
+    state: State<'_, AppState>,
+) -> Result<Response<KeyspaceSubscriptionInfo>, String> {
+    // 取出客户端和旧的订阅后立即释放连接表的锁，访问服务端期间不阻塞其他命令
+    let (client, db, previous) = {
+        let mut connections = state.connections.lock().unwrap();
+        match connections.get_mut(&connection_id) {
+            Some(conn_state) => (conn_state.client.clone(), conn_state.current_db, conn_state.keyspace.take()),
+            None => return Ok(Response::error("Redis 未连接".to_string())),
+        }
+    };
+
+    // 等旧的后台线程关闭订阅连接并恢复配置后再读取，避免旧订阅在替换后继续推送事件
+    if let Some(previous) = previous {
+        if let Err(e) = previous.shutdown() {
+            eprintln!("{}", e);
+        }
+    }
+
+    // 先建立订阅连接，开启配置后不会因为连接失败而无法恢复
+    let mut conn = match client.get_connection() {
+        Ok(conn) => conn,
+        Err(e) => return Ok(Response::error(format!("获取连接失败: {}", e))),
+    };
+    let mut config_conn = match client.get_connection() {
+        Ok(conn) => conn,
+        Err(e) => return Ok(Response::error(format!("获取连接失败: {}", e))),
+    };
+    // 托管服务通常禁用 CONFIG，此时依赖服务端已有的配置
+    let original = read_keyspace_events(&mut config_conn).ok();
+    let mut notify_keyspace_events = original.clone().unwrap_or_default();
+    let mut restore = None;
+    if let (true, Some(original)) = (enable_notifications, &original) {
+        match enable_keyspace_events(&mut config_conn, original) {
+            Ok(Some(flags)) => {
+                notify_keyspace_events = flags;
+                restore = Some((client.clone(), original.clone()));
+            }
+            Ok(None) => {}
+            Err(e) => return Ok(Response::error(e)),
+        }
+    }
+
+    let mut patterns: Vec<String> = keys
+        .unwrap_or_default()
+        .iter()
+        .map(|key| format!("__keyspace@{}__:{}", db, escape_glob(key)))
+        .collect();
+    if let Some(pattern) = pattern.filter(|p| !p.is_empty()) {
+        patterns.push(format!("__keyspace@{}__:{}", db, pattern));
+    }
+    if patterns.is_empty() {
+        patterns.push(format!("__keyspace@{}__:*", db));
+    }
+
+    let stop = Arc::new(AtomicBool::new(false));
+    let thread_stop = stop.clone();
+    let dead = Arc::new(AtomicBool::new(false));
+    let thread_dead = dead.clone();
+    let thread_patterns = patterns.clone();
+    let prefix = format!("__keyspace@{}__:", db);
+    let id = connection_id.clone();
+
+    // 后台线程出错退出时标记订阅已中断并通知前端，下次订阅或取消订阅时清除
+    let closed = move |app: &AppHandle, id: &str, error: String| {
+        thread_dead.store(true, Ordering::Relaxed);
+        let _ = app.emit(
+            KEYSPACE_CLOSED_EVENT,
+            KeyspaceClosed {
+                connection_id: id.to_string(),
+                error,
+            },
+        );
+    };
+
+    let listener = thread::spawn(move || {
+        let mut pubsub = conn.as_pubsub();
+        if let Err(e) = pubsub.psubscribe(&thread_patterns) {
+            closed(&app, &id, format!("订阅键空间通知失败: {}", e));
+            return;
+        }
+        // 读取超时用于定期检查是否已取消订阅
+        let _ = pubsub.set_read_timeout(Some(Duration::from_millis(500)));
+
+        while !thread_stop.load(Ordering::Relaxed) {
+            match pubsub.get_message() {
+                // 取消订阅后不再推送读取到的事件
+                Ok(_) if thread_stop.load(Ordering::Relaxed) => break,
+                Ok(message) => {
+                    let channel = message.get_channel_name();
+                    let Some(key) = channel.strip_prefix(&prefix) else {
+                        continue;
+                    };
+                    let event: String = message.get_payload().unwrap_or_default();
+                    let _ = app.emit(
+                        KEYSPACE_EVENT,
+                        KeyspaceEvent {
+                            connection_id: id.clone(),
+                            db,
+                            key: key.to_string(),
+                            event,
+                        },
+                    );
+                }
+                Err(e) if e.is_timeout() => {}
+                Err(e) => {
+                    closed(&app, &id, format!("接收键空间通知失败: {}", e));
+                    break;
+                }
+            }
+        }
+    });
+
+    let enabled_notifications = restore.is_some();
+    let subscription = KeyspaceSubscription {
+        stop,
+        dead,
+        listener: Some(listener),
+        restore,
+    };
+
+    // 重新加锁登记订阅；期间连接已断开时停止本次订阅，被并发的订阅替换时停止被替换的一方
+    let registered = {
+        let mut connections = state.connections.lock().unwrap();
+        match connections.get_mut(&connection_id) {
+            Some(conn_state) => Ok(conn_state.keyspace.replace(subscription)),
+            None => Err(subscription),
+        }
+    };
+    match registered {
+        Ok(Some(replaced)) => {
+            if let Err(e) = replaced.shutdown() {
+                eprintln!("{}", e);
+            }
+        }
+        Ok(None) => {}
+        Err(subscription) => {
+            let _ = subscription.shutdown();
+            return Ok(Response::error("Redis 未连接".to_string()));
+        }
+    }
+
+    let message = if original.is_none() {
+        "已订阅键空间通知，但无法读取 notify-keyspace-events，请确认服务端已开启".to_string()
+    } else if !keyspace_events_enabled(&notify_keyspace_events) {
+        format!(
+            "已订阅键空间通知，但服务端未开启（notify-keyspace-events: {}），收不到键变化事件",
+            notify_keyspace_events
+        )
+    } else if enabled_notifications {
+        format!(
+            "已订阅键空间通知，并临时开启了 notify-keyspace-events（{}），取消订阅后恢复为 {}",
+            notify_keyspace_events,
+            original.unwrap_or_default()
+        )
+    } else {
+        format!("已订阅键空间通知（notify-keyspace-events: {}）", notify_keyspace_events)
+    };
+    Ok(Response::success_with_message(
+        KeyspaceSubscriptionInfo {
+            db,
+            patterns,
+            notify_keyspace_events,
+            enabled_notifications,
+        },
+        message,
+    ))
+}
+
+// 取消连接上的键空间通知订阅，恢复订阅时修改的 notify-keyspace-events
+#[tauri::command]
+pub async fn unsubscribe_keyspace(
+    connection_id: String,
+    state: State<'_, AppState>,
+) -> Result<Response<()>, String> {
+    let subscription = {
+        let mut connections = state.connections.lock().unwrap();
+        match connections.get_mut(&connection_id) {
+            Some(conn_state) => conn_state.keyspace.take(),
+            None => return Ok(Response::error("Redis 未连接".to_string())),
+        }
+    };
+
+    // 释放连接表的锁后再等待后台线程退出并恢复配置
+    let Some(subscription) = subscription else {
+        return Ok(Response::error("当前连接没有键空间通知订阅".to_string()));
+    };
+    let dead = subscription.is_dead();
+    let message = match (subscription.shutdown(), dead) {
+        (Err(e), _) => format!("已取消键空间通知订阅，但{}", e),
+        (Ok(_), true) => "键空间通知订阅此前已因错误中断，已清除".to_string(),
+        (Ok(_), false) => "已取消键空间通知订阅".to_string(),
+    };
+    Ok(Response::<()>::success_empty_with_message(message))
+}
@@ -3,4 +3,16 @@ pub mod database;
 pub mod keys;
 pub mod server;
 pub mod import_export;
+pub mod set_ops;
+pub mod bitmap;
+pub mod hyperloglog;
+pub mod geo;
+pub mod decoder;
+pub mod protobuf;
+pub mod strings;
+pub mod numeric;
+pub mod transfer;
+pub mod bulk;
+pub mod analysis;
+pub mod keyspace;
 pub mod response;
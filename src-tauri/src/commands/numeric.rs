@@ -0,0 +1,181 @@
+use crate::state::AppState;
+use crate::commands::response::Response;
+use tauri::State;
+
+// 对整数值执行 INCRBY / DECRBY，返回运算后的值
+fn incr_internal(
+    state: &State<'_, AppState>,
+    connection_id: &str,
+    command: &str,
+    key: &str,
+    amount: i64,
+) -> Result<Response<i64>, String> {
+    let connections = state.connections.lock().unwrap();
+
+    if let Some(conn_state) = connections.get(connection_id) {
+        match conn_state.client.get_connection() {
+            Ok(mut conn) => {
+                let current_db = conn_state.current_db;
+                if let Err(e) = redis::cmd("SELECT").arg(current_db).query::<()>(&mut conn) {
+                    return Ok(Response::error(format!("切换到数据库 {} 失败: {}", current_db, e)));
+                }
+
+                match redis::cmd(command).arg(key).arg(amount).query::<i64>(&mut conn) {
+                    Ok(value) => Ok(Response::success_with_message(value, format!("{} 当前值为 {}", key, value))),
+                    Err(e) => Ok(Response::error(format!("{} 失败: {}", command, e))),
+                }
+            }
+            Err(e) => Ok(Response::error(format!("获取连接失败: {}", e))),
+        }
+    } else {
+        Ok(Response::error("Redis 未连接".to_string()))
+    }
+}
+
+// 整数自增
+#[tauri::command]
+pub async fn incr_by(
+    connection_id: String,
+    key: String,
+    increment: i64,
+    state: State<'_, AppState>,
+) -> Result<Response<i64>, String> {
+    incr_internal(&state, &connection_id, "INCRBY", &key, increment)
+}
+
+// 整数自减
+#[tauri::command]
+pub async fn decr_by(
+    connection_id: String,
+    key: String,
+    decrement: i64,
+    state: State<'_, AppState>,
+) -> Result<Response<i64>, String> {
+    incr_internal(&state, &connection_id, "DECRBY", &key, decrement)
+}
+
+// 浮点数自增，Redis 以字符串返回结果，原样返回以保留精度
+#[tauri::command]
+pub async fn incr_by_float(
+    connection_id: String,
+    key: String,
+    increment: f64,
+    state: State<'_, AppState>,
+) -> Result<Response<String>, String> {
+    let connections = state.connections.lock().unwrap();
+
+    if let Some(conn_state) = connections.get(&connection_id) {
+        match conn_state.client.get_connection() {
+            Ok(mut conn) => {
+                let current_db = conn_state.current_db;
+                if let Err(e) = redis::cmd("SELECT").arg(current_db).query::<()>(&mut conn) {
+                    return Ok(Response::error(format!("切换到数据库 {} 失败: {}", current_db, e)));
+                }
+
+                match redis::cmd("INCRBYFLOAT").arg(&key).arg(increment).query::<String>(&mut conn) {
+                    Ok(value) => Ok(Response::success_with_message(value.clone(), format!("{} 当前值为 {}", key, value))),
+                    Err(e) => Ok(Response::error(format!("INCRBYFLOAT 失败: {}", e))),
+                }
+            }
+            Err(e) => Ok(Response::error(format!("获取连接失败: {}", e))),
+        }
+    } else {
+        Ok(Response::error("Redis 未连接".to_string()))
+    }
+}
+
+// 哈希字段整数自增
+#[tauri::command]
+pub async fn hincr_by(
+    connection_id: String,
+    key: String,
+    field: String,
+    increment: i64,
+    state: State<'_, AppState>,
+) -> Result<Response<i64>, String> {
+    let connections = state.connections.lock().unwrap();
+
+    if let Some(conn_state) = connections.get(&connection_id) {
+        match conn_state.client.get_connection() {
+            Ok(mut conn) => {
+                let current_db = conn_state.current_db;
+                if let Err(e) = redis::cmd("SELECT").arg(current_db).query::<()>(&mut conn) {
+                    return Ok(Response::error(format!("切换到数据库 {} 失败: {}", current_db, e)));
+                }
+
+                match redis::cmd("HINCRBY").arg(&key).arg(&field).arg(increment).query::<i64>(&mut conn) {
+                    Ok(value) => Ok(Response::success_with_message(value, format!("{} 的字段 {} 当前值为 {}", key, field, value))),
+                    Err(e) => Ok(Response::error(format!("HINCRBY 失败: {}", e))),
+                }
+            }
+            Err(e) => Ok(Response::error(format!("获取连接失败: {}", e))),
+        }
+    } else {
+        Ok(Response::error("Redis 未连接".to_string()))
+    }
+}
+
+// 哈希字段浮点数自增
+#[tauri::command]
+pub async fn hincr_by_float(
+    connection_id: String,
+    key: String,
+    field: String,
+    increment: f64,
+    state: State<'_, AppState>,
+) -> Result<Response<String>, String> {
+    let connections = state.connections.lock().unwrap();
+
+    if let Some(conn_state) = connections.get(&connection_id) {
+        match conn_state.client.get_connection() {
+            Ok(mut conn) => {
+                let current_db = conn_state.current_db;
+                if let Err(e) = redis::cmd("SELECT").arg(current_db).query::<()>(&mut conn) {
+                    return Ok(Response::error(format!("切换到数据库 {} 失败: {}", current_db, e)));
+                }
+
+                match redis::cmd("HINCRBYFLOAT").arg(&key).arg(&field).arg(increment).query::<String>(&mut conn) {
+                    Ok(value) => Ok(Response::success_with_message(
+                        value.clone(),
+                        format!("{} 的字段 {} 当前值为 {}", key, field, value),
+                    )),
+                    Err(e) => Ok(Response::error(format!("HINCRBYFLOAT 失败: {}", e))),
+                }
+            }
+            Err(e) => Ok(Response::error(format!("获取连接失败: {}", e))),
+        }
+    } else {
+        Ok(Response::error("Redis 未连接".to_string()))
+    }
+}
+
+// 有序集合成员分数自增，返回新分数
+#[tauri::command]
+pub async fn zincr_by(
+    connection_id: String,
+    key: String,
+    member: String,
+    increment: f64,
+    state: State<'_, AppState>,
+) -> Result<Response<f64>, String> {
+    let connections = state.connections.lock().unwrap();
+
+    if let Some(conn_state) = connections.get(&connection_id) {
+        match conn_state.client.get_connection() {
+            Ok(mut conn) => {
+                let current_db = conn_state.current_db;
+                if let Err(e) = redis::cmd("SELECT").arg(current_db).query::<()>(&mut conn) {
+                    return Ok(Response::error(format!("切换到数据库 {} 失败: {}", current_db, e)));
+                }
+
+                match redis::cmd("ZINCRBY").arg(&key).arg(increment).arg(&member).query::<f64>(&mut conn) {
+                    Ok(score) => Ok(Response::success_with_message(score, format!("{} 的成员 {} 当前分数为 {}", key, member, score))),
+                    Err(e) => Ok(Response::error(format!("ZINCRBY 失败: {}", e))),
+                }
+            }
+            Err(e) => Ok(Response::error(format!("获取连接失败: {}", e))),
+        }
+    } else {
+        Ok(Response::error("Redis 未连接".to_string()))
+    }
+}
@@ -0,0 +1,141 @@
+use crate::state::AppState;
+use crate::commands::response::Response;
+use crate::decoder::protobuf::{compile_proto_files, ProtoMapping};
+use serde::Serialize;
+use tauri::State;
+
+// 连接的 Protobuf 配置
+#[derive(Debug, Serialize)]
+pub struct ProtoConfig {
+    pub message_types: Vec<String>,
+    pub mappings: Vec<ProtoMapping>,
+}
+
+// 注册 .proto 文件，include_paths 为 import 的查找目录（为空时使用各文件所在目录），返回已注册的全部消息类型
+#[tauri::command]
+pub async fn register_proto_files(
+    connection_id: String,
+    files: Vec<String>,
+    include_paths: Vec<String>,
+    state: State<'_, AppState>,
+) -> Result<Response<Vec<String>>, String> {
+    if files.is_empty() {
+        return Ok(Response::error("至少需要指定一个 proto 文件".to_string()));
+    }
+
+    let include_paths = if include_paths.is_empty() {
+        let mut dirs: Vec<String> = files
+            .iter()
+            .filter_map(|f| std::path::Path::new(f).parent())
+            .map(|dir| dir.to_string_lossy().to_string())
+            .collect();
+        dirs.sort();
+        dirs.dedup();
+        dirs
+    } else {
+        include_paths
+    };
+
+    // 编译可能需要较长时间，在获取连接锁之前完成，避免阻塞其他命令
+    let file_set = match compile_proto_files(&files, &include_paths) {
+        Ok(file_set) => file_set,
+        Err(e) => return Ok(Response::error(e)),
+    };
+
+    let mut connections = state.connections.lock().unwrap();
+
+    if let Some(conn_state) = connections.get_mut(&connection_id) {
+        match conn_state.proto.add_file_descriptor_set(file_set) {
+            Ok(_) => {
+                let message_types = conn_state.proto.message_types();
+                let message = format!("成功注册 {} 个 proto 文件，共 {} 个消息类型", files.len(), message_types.len());
+                Ok(Response::success_with_message(message_types, message))
+            }
+            Err(e) => Ok(Response::error(e)),
+        }
+    } else {
+        Ok(Response::error("Redis 未连接".to_string()))
+    }
+}
+
+// 注册编译好的 FileDescriptorSet（protoc --descriptor_set_out --include_imports 的输出），返回已注册的全部消息类型
+#[tauri::command]
+pub async fn register_descriptor_set(
+    connection_id: String,
+    path: String,
+    state: State<'_, AppState>,
+) -> Result<Response<Vec<String>>, String> {
+    let bytes = match std::fs::read(&path) {
+        Ok(bytes) => bytes,
+        Err(e) => return Ok(Response::error(format!("读取描述符文件失败: {}", e))),
+    };
+
+    let mut connections = state.connections.lock().unwrap();
+
+    if let Some(conn_state) = connections.get_mut(&connection_id) {
+        match conn_state.proto.add_descriptor_set(&bytes) {
+            Ok(_) => {
+                let message_types = conn_state.proto.message_types();
+                let message = format!("成功注册描述符，共 {} 个消息类型", message_types.len());
+                Ok(Response::success_with_message(message_types, message))
+            }
+            Err(e) => Ok(Response::error(e)),
+        }
+    } else {
+        Ok(Response::error("Redis 未连接".to_string()))
+    }
+}
+
+// 设置键模式到消息类型的映射，按顺序匹配，第一个匹配的映射生效
+#[tauri::command]
+pub async fn set_proto_mappings(
+    connection_id: String,
+    mappings: Vec<ProtoMapping>,
+    state: State<'_, AppState>,
+) -> Result<Response<()>, String> {
+    let mut connections = state.connections.lock().unwrap();
+
+    if let Some(conn_state) = connections.get_mut(&connection_id) {
+        let count = mappings.len();
+        match conn_state.proto.set_mappings(mappings) {
+            Ok(_) => Ok(Response::<()>::success_empty_with_message(format!("成功保存 {} 条映射", count))),
+            Err(e) => Ok(Response::error(e)),
+        }
+    } else {
+        Ok(Response::error("Redis 未连接".to_string()))
+    }
+}
+
+// 获取连接已注册的消息类型和映射
+#[tauri::command]
+pub async fn get_proto_config(
+    connection_id: String,
+    state: State<'_, AppState>,
+) -> Result<Response<ProtoConfig>, String> {
+    let connections = state.connections.lock().unwrap();
+
+    if let Some(conn_state) = connections.get(&connection_id) {
+        Ok(Response::success(ProtoConfig {
+            message_types: conn_state.proto.message_types(),
+            mappings: conn_state.proto.mappings().to_vec(),
+        }))
+    } else {
+        Ok(Response::error("Redis 未连接".to_string()))
+    }
+}
+
+// 清空连接已注册的描述符和映射
+#[tauri::command]
+pub async fn clear_proto_registry(
+    connection_id: String,
+    state: State<'_, AppState>,
+) -> Result<Response<()>, String> {
+    let mut connections = state.connections.lock().unwrap();
+
+    if let Some(conn_state) = connections.get_mut(&connection_id) {
+        conn_state.proto.clear();
+        Ok(Response::<()>::success_empty_with_message("已清空 Protobuf 配置".to_string()))
+    } else {
+        Ok(Response::error("Redis 未连接".to_string()))
+    }
+}
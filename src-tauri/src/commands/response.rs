@@ -56,5 +56,40 @@ impl<T> Response<T> {
             data: None,
         }
     }
+
+    /// 错误响应（带数据），例如编辑冲突时附带服务器上的当前值
+    pub fn error_with_data(data: T, message: String) -> Self {
+        Response {
+            success: false,
+            message,
+            data: Some(data),
+        }
+    }
 }
 
+/// 分页数据 - 用于集合运算、地理搜索等可能返回大量成员的接口
+#[derive(Debug, Serialize)]
+pub struct PageData<T> {
+    pub items: Vec<T>,
+    pub total: usize,
+    pub offset: usize,
+    pub count: usize,
+}
+
+impl<T> PageData<T> {
+    /// 从完整结果中截取 [offset, offset + count) 区间，count 为 0 时返回全部
+    pub fn paginate(all: Vec<T>, offset: usize, count: usize) -> Self {
+        let total = all.len();
+        let items: Vec<T> = if count == 0 {
+            all.into_iter().skip(offset).collect()
+        } else {
+            all.into_iter().skip(offset).take(count).collect()
+        };
+        PageData {
+            count: items.len(),
+            items,
+            total,
+            offset,
+        }
+    }
+}
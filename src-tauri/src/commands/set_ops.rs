@@ -0,0 +1,389 @@
+use crate::state::AppState;
+use crate::commands::response::{PageData, Response};
+use serde::Serialize;
+use tauri::State;
+
+// 有序集合运算结果项，字段与 get_key_detail 返回的 zset 项保持一致
+#[derive(Debug, Serialize)]
+pub struct ZSetItem {
+    pub value: String,
+    pub score: f64,
+}
+
+// 解析集合运算类型，返回命令前缀（SINTER / SUNION / SDIFF 去掉首字母 S 后的部分）
+fn parse_set_op(op: &str) -> Option<&'static str> {
+    match op.to_lowercase().as_str() {
+        "inter" => Some("INTER"),
+        "union" => Some("UNION"),
+        "diff" => Some("DIFF"),
+        _ => None,
+    }
+}
+
+// 解析聚合方式
+fn parse_aggregate(aggregate: &str) -> Option<&'static str> {
+    match aggregate.to_lowercase().as_str() {
+        "sum" => Some("SUM"),
+        "min" => Some("MIN"),
+        "max" => Some("MAX"),
+        _ => None,
+    }
+}
+
+// 拼接 ZINTER/ZUNION/ZDIFF 及其 STORE 变体的公共参数
+fn append_zset_op_args(
+    cmd: &mut redis::Cmd,
+    op: &str,
+    keys: &[String],
+    weights: &Option<Vec<f64>>,
+    aggregate: &Option<String>,
+) -> Result<(), String> {
+    cmd.arg(keys.len());
+    for key in keys {
+        cmd.arg(key);
+    }
+
+    // ZDIFF 不支持 WEIGHTS 和 AGGREGATE
+    if op == "DIFF" && (weights.is_some() || aggregate.is_some()) {
+        return Err("差集运算不支持权重和聚合方式".to_string());
+    }
+
+    if let Some(weights) = weights {
+        if weights.len() != keys.len() {
+            return Err(format!("权重数量 {} 与键数量 {} 不一致", weights.len(), keys.len()));
+        }
+        cmd.arg("WEIGHTS");
+        for weight in weights {
+            cmd.arg(*weight);
+        }
+    }
+
+    if let Some(aggregate) = aggregate {
+        let aggregate_arg = parse_aggregate(aggregate)
+            .ok_or_else(|| format!("不支持的聚合方式: {}", aggregate))?;
+        cmd.arg("AGGREGATE").arg(aggregate_arg);
+    }
+
+    Ok(())
+}
+
+// 集合运算（SINTER / SUNION / SDIFF），结果按成员排序后分页返回
+#[tauri::command]
+pub async fn set_operation(
+    connection_id: String,
+    op: String,
+    keys: Vec<String>,
+    offset: usize,
+    count: usize,
+    state: State<'_, AppState>,
+) -> Result<Response<PageData<String>>, String> {
+    let op_name = match parse_set_op(&op) {
+        Some(name) => name,
+        None => return Ok(Response::error(format!("不支持的集合运算: {}", op))),
+    };
+
+    if keys.is_empty() {
+        return Ok(Response::error("至少需要一个键".to_string()));
+    }
+
+    let connections = state.connections.lock().unwrap();
+
+    if let Some(conn_state) = connections.get(&connection_id) {
+        match conn_state.client.get_connection() {
+            Ok(mut conn) => {
+                let current_db = conn_state.current_db;
+                if let Err(e) = redis::cmd("SELECT").arg(current_db).query::<()>(&mut conn) {
+                    return Ok(Response::error(format!("切换到数据库 {} 失败: {}", current_db, e)));
+                }
+
+                let mut cmd = redis::cmd(&format!("S{}", op_name));
+                for key in &keys {
+                    cmd.arg(key);
+                }
+
+                match cmd.query::<Vec<String>>(&mut conn) {
+                    Ok(mut members) => {
+                        // 集合本身无序，排序后分页结果才稳定
+                        members.sort();
+                        Ok(Response::success(PageData::paginate(members, offset, count)))
+                    }
+                    Err(e) => Ok(Response::error(format!("集合运算失败: {}", e))),
+                }
+            }
+            Err(e) => Ok(Response::error(format!("获取连接失败: {}", e))),
+        }
+    } else {
+        Ok(Response::error("Redis 未连接".to_string()))
+    }
+}
+
+// 集合运算并保存到目标键（SINTERSTORE / SUNIONSTORE / SDIFFSTORE）
+#[tauri::command]
+pub async fn set_operation_store(
+    connection_id: String,
+    op: String,
+    destination: String,
+    keys: Vec<String>,
+    state: State<'_, AppState>,
+) -> Result<Response<usize>, String> {
+    let op_name = match parse_set_op(&op) {
+        Some(name) => name,
+        None => return Ok(Response::error(format!("不支持的集合运算: {}", op))),
+    };
+
+    if keys.is_empty() {
+        return Ok(Response::error("至少需要一个键".to_string()));
+    }
+
+    let connections = state.connections.lock().unwrap();
+
+    if let Some(conn_state) = connections.get(&connection_id) {
+        match conn_state.client.get_connection() {
+            Ok(mut conn) => {
+                let current_db = conn_state.current_db;
+                if let Err(e) = redis::cmd("SELECT").arg(current_db).query::<()>(&mut conn) {
+                    return Ok(Response::error(format!("切换到数据库 {} 失败: {}", current_db, e)));
+                }
+
+                let mut cmd = redis::cmd(&format!("S{}STORE", op_name));
+                cmd.arg(&destination);
+                for key in &keys {
+                    cmd.arg(key);
+                }
+
+                match cmd.query::<usize>(&mut conn) {
+                    Ok(size) => Ok(Response::success_with_message(size, format!("结果已保存到 {}，共 {} 个元素", destination, size))),
+                    Err(e) => Ok(Response::error(format!("集合运算失败: {}", e))),
+                }
+            }
+            Err(e) => Ok(Response::error(format!("获取连接失败: {}", e))),
+        }
+    } else {
+        Ok(Response::error("Redis 未连接".to_string()))
+    }
+}
+
+// 计算交集基数（SINTERCARD），limit 为 0 表示不限制
+#[tauri::command]
+pub async fn set_intercard(
+    connection_id: String,
+    keys: Vec<String>,
+    limit: usize,
+    state: State<'_, AppState>,
+) -> Result<Response<usize>, String> {
+    if keys.is_empty() {
+        return Ok(Response::error("至少需要一个键".to_string()));
+    }
+
+    let connections = state.connections.lock().unwrap();
+
+    if let Some(conn_state) = connections.get(&connection_id) {
+        match conn_state.client.get_connection() {
+            Ok(mut conn) => {
+                let current_db = conn_state.current_db;
+                if let Err(e) = redis::cmd("SELECT").arg(current_db).query::<()>(&mut conn) {
+                    return Ok(Response::error(format!("切换到数据库 {} 失败: {}", current_db, e)));
+                }
+
+                let mut cmd = redis::cmd("SINTERCARD");
+                cmd.arg(keys.len());
+                for key in &keys {
+                    cmd.arg(key);
+                }
+                if limit > 0 {
+                    cmd.arg("LIMIT").arg(limit);
+                }
+
+                match cmd.query::<usize>(&mut conn) {
+                    Ok(card) => Ok(Response::success(card)),
+                    Err(e) => Ok(Response::error(format!("计算交集基数失败: {}", e))),
+                }
+            }
+            Err(e) => Ok(Response::error(format!("获取连接失败: {}", e))),
+        }
+    } else {
+        Ok(Response::error("Redis 未连接".to_string()))
+    }
+}
+
+// 批量判断成员是否属于集合（SMISMEMBER）
+#[tauri::command]
+pub async fn set_is_members(
+    connection_id: String,
+    key: String,
+    members: Vec<String>,
+    state: State<'_, AppState>,
+) -> Result<Response<Vec<bool>>, String> {
+    if members.is_empty() {
+        return Ok(Response::success(Vec::new()));
+    }
+
+    let connections = state.connections.lock().unwrap();
+
+    if let Some(conn_state) = connections.get(&connection_id) {
+        match conn_state.client.get_connection() {
+            Ok(mut conn) => {
+                let current_db = conn_state.current_db;
+                if let Err(e) = redis::cmd("SELECT").arg(current_db).query::<()>(&mut conn) {
+                    return Ok(Response::error(format!("切换到数据库 {} 失败: {}", current_db, e)));
+                }
+
+                let mut cmd = redis::cmd("SMISMEMBER");
+                cmd.arg(&key);
+                for member in &members {
+                    cmd.arg(member);
+                }
+
+                match cmd.query::<Vec<bool>>(&mut conn) {
+                    Ok(flags) => Ok(Response::success(flags)),
+                    Err(e) => Ok(Response::error(format!("判断成员失败: {}", e))),
+                }
+            }
+            Err(e) => Ok(Response::error(format!("获取连接失败: {}", e))),
+        }
+    } else {
+        Ok(Response::error("Redis 未连接".to_string()))
+    }
+}
+
+// 将成员从一个集合移动到另一个集合（SMOVE）
+#[tauri::command]
+pub async fn move_set_item(
+    connection_id: String,
+    source: String,
+    destination: String,
+    member: String,
+    state: State<'_, AppState>,
+) -> Result<Response<()>, String> {
+    let connections = state.connections.lock().unwrap();
+
+    if let Some(conn_state) = connections.get(&connection_id) {
+        match conn_state.client.get_connection() {
+            Ok(mut conn) => {
+                let current_db = conn_state.current_db;
+                if let Err(e) = redis::cmd("SELECT").arg(current_db).query::<()>(&mut conn) {
+                    return Ok(Response::error(format!("切换到数据库 {} 失败: {}", current_db, e)));
+                }
+
+                match redis::cmd("SMOVE")
+                    .arg(&source)
+                    .arg(&destination)
+                    .arg(&member)
+                    .query::<i64>(&mut conn)
+                {
+                    Ok(1) => Ok(Response::<()>::success_empty_with_message(format!("成功将元素从 {} 移动到 {}", source, destination))),
+                    Ok(_) => Ok(Response::error(format!("元素不存在于集合 {}", source))),
+                    Err(e) => Ok(Response::error(format!("移动失败: {}", e))),
+                }
+            }
+            Err(e) => Ok(Response::error(format!("获取连接失败: {}", e))),
+        }
+    } else {
+        Ok(Response::error("Redis 未连接".to_string()))
+    }
+}
+
+// 有序集合运算（ZINTER / ZUNION / ZDIFF），结果按分数排序后分页返回
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+pub async fn zset_operation(
+    connection_id: String,
+    op: String,
+    keys: Vec<String>,
+    weights: Option<Vec<f64>>,
+    aggregate: Option<String>,
+    offset: usize,
+    count: usize,
+    state: State<'_, AppState>,
+) -> Result<Response<PageData<ZSetItem>>, String> {
+    let op_name = match parse_set_op(&op) {
+        Some(name) => name,
+        None => return Ok(Response::error(format!("不支持的有序集合运算: {}", op))),
+    };
+
+    if keys.is_empty() {
+        return Ok(Response::error("至少需要一个键".to_string()));
+    }
+
+    let mut cmd = redis::cmd(&format!("Z{}", op_name));
+    if let Err(e) = append_zset_op_args(&mut cmd, op_name, &keys, &weights, &aggregate) {
+        return Ok(Response::error(e));
+    }
+    cmd.arg("WITHSCORES");
+
+    let connections = state.connections.lock().unwrap();
+
+    if let Some(conn_state) = connections.get(&connection_id) {
+        match conn_state.client.get_connection() {
+            Ok(mut conn) => {
+                let current_db = conn_state.current_db;
+                if let Err(e) = redis::cmd("SELECT").arg(current_db).query::<()>(&mut conn) {
+                    return Ok(Response::error(format!("切换到数据库 {} 失败: {}", current_db, e)));
+                }
+
+                // 服务端已按分数升序返回，直接分页
+                match cmd.query::<Vec<(String, f64)>>(&mut conn) {
+                    Ok(items) => {
+                        let items: Vec<ZSetItem> = items
+                            .into_iter()
+                            .map(|(value, score)| ZSetItem { value, score })
+                            .collect();
+                        Ok(Response::success(PageData::paginate(items, offset, count)))
+                    }
+                    Err(e) => Ok(Response::error(format!("有序集合运算失败: {}", e))),
+                }
+            }
+            Err(e) => Ok(Response::error(format!("获取连接失败: {}", e))),
+        }
+    } else {
+        Ok(Response::error("Redis 未连接".to_string()))
+    }
+}
+
+// 有序集合运算并保存到目标键（ZINTERSTORE / ZUNIONSTORE / ZDIFFSTORE）
+#[tauri::command]
+pub async fn zset_operation_store(
+    connection_id: String,
+    op: String,
+    destination: String,
+    keys: Vec<String>,
+    weights: Option<Vec<f64>>,
+    aggregate: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<Response<usize>, String> {
+    let op_name = match parse_set_op(&op) {
+        Some(name) => name,
+        None => return Ok(Response::error(format!("不支持的有序集合运算: {}", op))),
+    };
+
+    if keys.is_empty() {
+        return Ok(Response::error("至少需要一个键".to_string()));
+    }
+
+    let mut cmd = redis::cmd(&format!("Z{}STORE", op_name));
+    cmd.arg(&destination);
+    if let Err(e) = append_zset_op_args(&mut cmd, op_name, &keys, &weights, &aggregate) {
+        return Ok(Response::error(e));
+    }
+
+    let connections = state.connections.lock().unwrap();
+
+    if let Some(conn_state) = connections.get(&connection_id) {
+        match conn_state.client.get_connection() {
+            Ok(mut conn) => {
+                let current_db = conn_state.current_db;
+                if let Err(e) = redis::cmd("SELECT").arg(current_db).query::<()>(&mut conn) {
+                    return Ok(Response::error(format!("切换到数据库 {} 失败: {}", current_db, e)));
+                }
+
+                match cmd.query::<usize>(&mut conn) {
+                    Ok(size) => Ok(Response::success_with_message(size, format!("结果已保存到 {}，共 {} 个元素", destination, size))),
+                    Err(e) => Ok(Response::error(format!("有序集合运算失败: {}", e))),
+                }
+            }
+            Err(e) => Ok(Response::error(format!("获取连接失败: {}", e))),
+        }
+    } else {
+        Ok(Response::error("Redis 未连接".to_string()))
+    }
+}
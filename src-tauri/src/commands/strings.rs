@@ -0,0 +1,425 @@
+use crate::state::AppState;
+use crate::commands::response::Response;
+use crate::commands::keys::{temp_key_for, TEMP_KEY_TTL_MS};
+use base64::Engine;
+use redis::Connection;
+use serde::Serialize;
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Read, Write};
+use tauri::State;
+
+// 超过该长度的字符串在详情中只返回开头部分作为预览
+pub const STRING_PREVIEW_BYTES: usize = 256 * 1024;
+
+// 单次读取的最大窗口，也是下载/上传时每个分块的大小
+const MAX_RANGE_BYTES: usize = 4 * 1024 * 1024;
+
+// 字符串区间数据
+#[derive(Debug, Serialize)]
+pub struct StringRange {
+    pub offset: usize,
+    pub length: usize,
+    pub total_length: usize,
+    pub data: String, // 按 encoding 编码后的内容
+}
+
+// 字符串超过预览长度时返回 (预览内容, 总长度)，否则返回 None 由调用方直接 GET
+pub fn read_string_preview(conn: &mut Connection, key: &str) -> Result<Option<(String, usize)>, String> {
+    let total = redis::cmd("STRLEN")
+        .arg(key)
+        .query::<usize>(conn)
+        .map_err(|e| format!("获取字符串长度失败: {}", e))?;
+
+    if total <= STRING_PREVIEW_BYTES {
+        return Ok(None);
+    }
+
+    let bytes = redis::cmd("GETRANGE")
+        .arg(key)
+        .arg(0)
+        .arg(STRING_PREVIEW_BYTES - 1)
+        .query::<Vec<u8>>(conn)
+        .map_err(|e| format!("获取字符串预览失败: {}", e))?;
+
+    Ok(Some((String::from_utf8_lossy(&bytes).into_owned(), total)))
+}
+
+// 已有字符串超过预览长度时拒绝整体覆盖：详情只加载了预览，直接保存会截断原值
+// 键不存在或不是字符串时允许写入
+pub fn ensure_not_truncated(conn: &mut Connection, key: &str) -> Result<(), String> {
+    match redis::cmd("STRLEN").arg(key).query::<usize>(conn) {
+        Ok(total) if total > STRING_PREVIEW_BYTES => Err(format!(
+            "{} 共 {} 字节，详情中只加载了前 {} 字节预览，不能整体覆盖；请使用分段写入或上传文件修改",
+            key, total, STRING_PREVIEW_BYTES
+        )),
+        Ok(_) => Ok(()),
+        Err(e) if e.code() == Some("WRONGTYPE") => Ok(()),
+        Err(e) => Err(format!("获取字符串长度失败: {}", e)),
+    }
+}
+
+// 按 encoding（text / hex / base64）把字节编码为字符串，text 模式下无效的 UTF-8 会被替换
+fn encode_data(encoding: Option<&str>, bytes: &[u8]) -> Result<String, String> {
+    match encoding.unwrap_or("text") {
+        "text" => Ok(String::from_utf8_lossy(bytes).into_owned()),
+        "hex" => Ok(hex::encode(bytes)),
+        "base64" => Ok(base64::engine::general_purpose::STANDARD.encode(bytes)),
+        other => Err(format!("不支持的编码: {}", other)),
+    }
+}
+
+fn decode_data(encoding: Option<&str>, data: &str) -> Result<Vec<u8>, String> {
+    match encoding.unwrap_or("text") {
+        "text" => Ok(data.as_bytes().to_vec()),
+        "hex" => hex::decode(data.trim()).map_err(|e| format!("十六进制解析失败: {}", e)),
+        "base64" => base64::engine::general_purpose::STANDARD
+            .decode(data.trim())
+            .map_err(|e| format!("base64 解码失败: {}", e)),
+        other => Err(format!("不支持的编码: {}", other)),
+    }
+}
+
+// 读取字符串的任意字节区间
+#[tauri::command]
+pub async fn get_string_range(
+    connection_id: String,
+    key: String,
+    offset: usize,
+    length: usize,
+    encoding: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<Response<StringRange>, String> {
+    if length == 0 || length > MAX_RANGE_BYTES {
+        return Ok(Response::error(format!("单次读取长度必须在 1 到 {} 字节之间", MAX_RANGE_BYTES)));
+    }
+
+    let connections = state.connections.lock().unwrap();
+
+    if let Some(conn_state) = connections.get(&connection_id) {
+        match conn_state.client.get_connection() {
+            Ok(mut conn) => {
+                let current_db = conn_state.current_db;
+                if let Err(e) = redis::cmd("SELECT").arg(current_db).query::<()>(&mut conn) {
+                    return Ok(Response::error(format!("切换到数据库 {} 失败: {}", current_db, e)));
+                }
+
+                let total_length = match redis::cmd("STRLEN").arg(&key).query::<usize>(&mut conn) {
+                    Ok(len) => len,
+                    Err(e) => return Ok(Response::error(format!("获取字符串长度失败: {}", e))),
+                };
+
+                let bytes = if offset >= total_length {
+                    Vec::new()
+                } else {
+                    match redis::cmd("GETRANGE")
+                        .arg(&key)
+                        .arg(offset)
+                        .arg(offset + length - 1)
+                        .query::<Vec<u8>>(&mut conn)
+                    {
+                        Ok(bytes) => bytes,
+                        Err(e) => return Ok(Response::error(format!("读取字符串区间失败: {}", e))),
+                    }
+                };
+
+                match encode_data(encoding.as_deref(), &bytes) {
+                    Ok(data) => Ok(Response::success(StringRange {
+                        offset,
+                        length: bytes.len(),
+                        total_length,
+                        data,
+                    })),
+                    Err(e) => Ok(Response::error(e)),
+                }
+            }
+            Err(e) => Ok(Response::error(format!("获取连接失败: {}", e))),
+        }
+    } else {
+        Ok(Response::error("Redis 未连接".to_string()))
+    }
+}
+
+// 从指定偏移覆盖写入，返回写入后的字符串长度
+#[tauri::command]
+pub async fn set_string_range(
+    connection_id: String,
+    key: String,
+    offset: usize,
+    data: String,
+    encoding: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<Response<usize>, String> {
+    let bytes = match decode_data(encoding.as_deref(), &data) {
+        Ok(bytes) => bytes,
+        Err(e) => return Ok(Response::error(e)),
+    };
+
+    let connections = state.connections.lock().unwrap();
+
+    if let Some(conn_state) = connections.get(&connection_id) {
+        match conn_state.client.get_connection() {
+            Ok(mut conn) => {
+                let current_db = conn_state.current_db;
+                if let Err(e) = redis::cmd("SELECT").arg(current_db).query::<()>(&mut conn) {
+                    return Ok(Response::error(format!("切换到数据库 {} 失败: {}", current_db, e)));
+                }
+
+                match redis::cmd("SETRANGE")
+                    .arg(&key)
+                    .arg(offset)
+                    .arg(&bytes)
+                    .query::<usize>(&mut conn)
+                {
+                    Ok(len) => Ok(Response::success_with_message(len, format!("成功写入 {} 字节", bytes.len()))),
+                    Err(e) => Ok(Response::error(format!("写入失败: {}", e))),
+                }
+            }
+            Err(e) => Ok(Response::error(format!("获取连接失败: {}", e))),
+        }
+    } else {
+        Ok(Response::error("Redis 未连接".to_string()))
+    }
+}
+
+// 在字符串末尾追加内容，返回追加后的字符串长度
+#[tauri::command]
+pub async fn append_string(
+    connection_id: String,
+    key: String,
+    data: String,
+    encoding: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<Response<usize>, String> {
+    let bytes = match decode_data(encoding.as_deref(), &data) {
+        Ok(bytes) => bytes,
+        Err(e) => return Ok(Response::error(e)),
+    };
+
+    let connections = state.connections.lock().unwrap();
+
+    if let Some(conn_state) = connections.get(&connection_id) {
+        match conn_state.client.get_connection() {
+            Ok(mut conn) => {
+                let current_db = conn_state.current_db;
+                if let Err(e) = redis::cmd("SELECT").arg(current_db).query::<()>(&mut conn) {
+                    return Ok(Response::error(format!("切换到数据库 {} 失败: {}", current_db, e)));
+                }
+
+                match redis::cmd("APPEND").arg(&key).arg(&bytes).query::<usize>(&mut conn) {
+                    Ok(len) => Ok(Response::success_with_message(len, format!("成功追加 {} 字节", bytes.len()))),
+                    Err(e) => Ok(Response::error(format!("追加失败: {}", e))),
+                }
+            }
+            Err(e) => Ok(Response::error(format!("获取连接失败: {}", e))),
+        }
+    } else {
+        Ok(Response::error("Redis 未连接".to_string()))
+    }
+}
+
+// 分块读取整个字符串值并写入本地文件，返回写入的字节数
+#[tauri::command]
+pub async fn download_string_value(
+    connection_id: String,
+    key: String,
+    path: String,
+    state: State<'_, AppState>,
+) -> Result<Response<usize>, String> {
+    // 只复制客户端，传输文件期间不占用连接表的锁
+    let (client, current_db) = {
+        let connections = state.connections.lock().unwrap();
+        match connections.get(&connection_id) {
+            Some(conn_state) => (conn_state.client.clone(), conn_state.current_db),
+            None => return Ok(Response::error("Redis 未连接".to_string())),
+        }
+    };
+
+    let mut conn = match client.get_connection() {
+        Ok(conn) => conn,
+        Err(e) => return Ok(Response::error(format!("获取连接失败: {}", e))),
+    };
+    if let Err(e) = redis::cmd("SELECT").arg(current_db).query::<()>(&mut conn) {
+        return Ok(Response::error(format!("切换到数据库 {} 失败: {}", current_db, e)));
+    }
+
+    let key_type = match redis::cmd("TYPE").arg(&key).query::<String>(&mut conn) {
+        Ok(t) => t,
+        Err(e) => return Ok(Response::error(format!("获取键类型失败: {}", e))),
+    };
+    if key_type != "string" {
+        return Ok(Response::error(format!("键 {} 不是字符串类型", key)));
+    }
+
+    let total = match redis::cmd("STRLEN").arg(&key).query::<usize>(&mut conn) {
+        Ok(len) => len,
+        Err(e) => return Ok(Response::error(format!("获取字符串长度失败: {}", e))),
+    };
+
+    let mut writer = match File::create(&path) {
+        Ok(file) => BufWriter::new(file),
+        Err(e) => return Ok(Response::error(format!("创建文件失败: {}", e))),
+    };
+
+    let mut written = 0;
+    while written < total {
+        let chunk = match redis::cmd("GETRANGE")
+            .arg(&key)
+            .arg(written)
+            .arg(written + MAX_RANGE_BYTES - 1)
+            .query::<Vec<u8>>(&mut conn)
+        {
+            Ok(chunk) => chunk,
+            Err(e) => return Ok(Response::error(format!("读取第 {} 字节处的分块失败: {}", written, e))),
+        };
+        // 下载过程中值被截短时提前结束
+        if chunk.is_empty() {
+            break;
+        }
+        if let Err(e) = writer.write_all(&chunk) {
+            return Ok(Response::error(format!("写入文件失败: {}", e)));
+        }
+        written += chunk.len();
+    }
+
+    if let Err(e) = writer.flush() {
+        return Ok(Response::error(format!("写入文件失败: {}", e)));
+    }
+
+    Ok(Response::success_with_message(written, format!("成功下载 {} 字节到 {}", written, path)))
+}
+
+// 分块读取本地文件并上传为字符串值，返回上传的字节数
+// 先写入临时键，全部分块成功后再 RENAME 覆盖目标键，中途失败不会留下不完整的值；已有的 TTL 会被保留
+#[tauri::command]
+pub async fn upload_string_value(
+    connection_id: String,
+    key: String,
+    path: String,
+    state: State<'_, AppState>,
+) -> Result<Response<usize>, String> {
+    let mut reader = match File::open(&path) {
+        Ok(file) => BufReader::new(file),
+        Err(e) => return Ok(Response::error(format!("打开文件失败: {}", e))),
+    };
+
+    // 只复制客户端，传输文件期间不占用连接表的锁
+    let (client, current_db) = {
+        let connections = state.connections.lock().unwrap();
+        match connections.get(&connection_id) {
+            Some(conn_state) => (conn_state.client.clone(), conn_state.current_db),
+            None => return Ok(Response::error("Redis 未连接".to_string())),
+        }
+    };
+
+    let mut conn = match client.get_connection() {
+        Ok(conn) => conn,
+        Err(e) => return Ok(Response::error(format!("获取连接失败: {}", e))),
+    };
+    if let Err(e) = redis::cmd("SELECT").arg(current_db).query::<()>(&mut conn) {
+        return Ok(Response::error(format!("切换到数据库 {} 失败: {}", current_db, e)));
+    }
+
+    // 临时键与目标键位于同一槽位，并带有较短的过期时间，上传中途退出时由服务端清除
+    let temp_key = temp_key_for("upload", &key);
+
+    if let Err(e) = redis::cmd("SET").arg(&temp_key).arg("").arg("PX").arg(TEMP_KEY_TTL_MS).query::<()>(&mut conn) {
+        return Ok(Response::error(format!("创建临时键失败: {}", e)));
+    }
+
+    let mut buf = vec![0u8; MAX_RANGE_BYTES];
+    let mut uploaded = 0;
+
+    loop {
+        let n = match reader.read(&mut buf) {
+            Ok(n) => n,
+            Err(e) => {
+                let _ = redis::cmd("DEL").arg(&temp_key).query::<()>(&mut conn);
+                return Ok(Response::error(format!("读取文件失败: {}", e)));
+            }
+        };
+        if n == 0 {
+            break;
+        }
+        // 每个分块写入时同时续期临时键
+        let mut append = redis::pipe();
+        append
+            .cmd("APPEND").arg(&temp_key).arg(&buf[..n]).ignore()
+            .cmd("PEXPIRE").arg(&temp_key).arg(TEMP_KEY_TTL_MS).ignore();
+        if let Err(e) = append.query::<()>(&mut conn) {
+            let _ = redis::cmd("DEL").arg(&temp_key).query::<()>(&mut conn);
+            return Ok(Response::error(format!("上传第 {} 字节处的分块失败: {}", uploaded, e)));
+        }
+        uploaded += n;
+    }
+
+    // 上传完成后再读取原键的剩余过期时间，避免上传耗时延长原键的寿命
+    let pttl = match redis::cmd("PTTL").arg(&key).query::<i64>(&mut conn) {
+        Ok(pttl) => pttl,
+        Err(e) => {
+            let _ = redis::cmd("DEL").arg(&temp_key).query::<()>(&mut conn);
+            return Ok(Response::error(format!("获取TTL失败: {}", e)));
+        }
+    };
+
+    let mut pipe = redis::pipe();
+    pipe.atomic().cmd("RENAME").arg(&temp_key).arg(&key).ignore();
+    if pttl > 0 {
+        pipe.cmd("PEXPIRE").arg(&key).arg(pttl).ignore();
+    } else {
+        pipe.cmd("PERSIST").arg(&key).ignore();
+    }
+    if let Err(e) = pipe.query::<()>(&mut conn) {
+        let _ = redis::cmd("DEL").arg(&temp_key).query::<()>(&mut conn);
+        return Ok(Response::error(format!("保存上传内容失败: {}", e)));
+    }
+
+    Ok(Response::success_with_message(uploaded, format!("成功从 {} 上传 {} 字节", path, uploaded)))
+}
+
+// 更新字符串值并保留原有 TTL（SET ... KEEPTTL），return_old 为 true 时同时返回旧值（需要 Redis 6.2+）
+#[tauri::command]
+pub async fn set_string_value(
+    connection_id: String,
+    key: String,
+    value: String,
+    return_old: bool,
+    state: State<'_, AppState>,
+) -> Result<Response<Option<String>>, String> {
+    let connections = state.connections.lock().unwrap();
+
+    if let Some(conn_state) = connections.get(&connection_id) {
+        match conn_state.client.get_connection() {
+            Ok(mut conn) => {
+                let current_db = conn_state.current_db;
+                if let Err(e) = redis::cmd("SELECT").arg(current_db).query::<()>(&mut conn) {
+                    return Ok(Response::error(format!("切换到数据库 {} 失败: {}", current_db, e)));
+                }
+
+                if let Err(e) = ensure_not_truncated(&mut conn, &key) {
+                    return Ok(Response::error(e));
+                }
+
+                let mut cmd = redis::cmd("SET");
+                cmd.arg(&key).arg(&value).arg("KEEPTTL");
+                if return_old {
+                    cmd.arg("GET");
+                }
+
+                let result = if return_old {
+                    cmd.query::<Option<Vec<u8>>>(&mut conn)
+                        .map(|old| old.map(|bytes| String::from_utf8_lossy(&bytes).into_owned()))
+                } else {
+                    cmd.query::<()>(&mut conn).map(|_| None)
+                };
+
+                match result {
+                    Ok(old) => Ok(Response::success_with_message(old, format!("成功更新 {}", key))),
+                    Err(e) => Ok(Response::error(format!("更新失败: {}", e))),
+                }
+            }
+            Err(e) => Ok(Response::error(format!("获取连接失败: {}", e))),
+        }
+    } else {
+        Ok(Response::error("Redis 未连接".to_string()))
+    }
+}
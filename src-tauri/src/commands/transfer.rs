@@ -0,0 +1,390 @@
+use crate::state::AppState;
+use crate::commands::response::Response;
+use crate::commands::keys::is_unknown_command;
+use serde::Serialize;
+use tauri::State;
+
+// 跨连接迁移结果
+#[derive(Debug, Serialize)]
+pub struct TransferResult {
+    pub transferred: usize,
+    pub skipped: Vec<String>, // 目标已存在且未指定覆盖的键
+    pub failed: Vec<String>,  // 迁移失败的键及原因
+}
+
+// 复制键到当前库或其他库，优先使用 COPY（Redis 6.2+），不支持时回退为 DUMP/RESTORE
+#[tauri::command]
+pub async fn copy_key(
+    connection_id: String,
+    source_key: String,
+    target_key: String,
+    target_db: Option<u8>, // 为空时复制到当前库
+    replace: bool,
+    state: State<'_, AppState>,
+) -> Result<Response<()>, String> {
+    let connections = state.connections.lock().unwrap();
+
+    if let Some(conn_state) = connections.get(&connection_id) {
+        match conn_state.client.get_connection() {
+            Ok(mut conn) => {
+                let current_db = conn_state.current_db;
+                if let Err(e) = redis::cmd("SELECT").arg(current_db).query::<()>(&mut conn) {
+                    return Ok(Response::error(format!("切换到数据库 {} 失败: {}", current_db, e)));
+                }
+
+                let target_db = target_db.unwrap_or(current_db);
+                if target_db == current_db && source_key == target_key {
+                    return Ok(Response::error("源键和目标键相同".to_string()));
+                }
+
+                let mut cmd = redis::cmd("COPY");
+                cmd.arg(&source_key).arg(&target_key);
+                if target_db != current_db {
+                    cmd.arg("DB").arg(target_db);
+                }
+                if replace {
+                    cmd.arg("REPLACE");
+                }
+
+                let copied = match cmd.query::<bool>(&mut conn) {
+                    Ok(copied) => copied,
+                    Err(e) if is_unknown_command(&e) => {
+                        match copy_by_dump(&mut conn, &source_key, &target_key, current_db, target_db, replace) {
+                            Ok(copied) => copied,
+                            Err(e) => return Ok(Response::error(e)),
+                        }
+                    }
+                    Err(e) => return Ok(Response::error(format!("复制失败: {}", e))),
+                };
+
+                if copied {
+                    Ok(Response::<()>::success_empty_with_message(format!(
+                        "成功复制 {} 到数据库 {} 的 {}",
+                        source_key, target_db, target_key
+                    )))
+                } else {
+                    Ok(Response::error(format!(
+                        "复制失败: 键 {} 不存在或数据库 {} 中已存在 {}",
+                        source_key, target_db, target_key
+                    )))
+                }
+            }
+            Err(e) => Ok(Response::error(format!("获取连接失败: {}", e))),
+        }
+    } else {
+        Ok(Response::error("Redis 未连接".to_string()))
+    }
+}
+
+// 移动键到其他库，目标库已存在同名键时不移动
+#[tauri::command]
+pub async fn move_key(
+    connection_id: String,
+    key: String,
+    target_db: u8,
+    state: State<'_, AppState>,
+) -> Result<Response<()>, String> {
+    let connections = state.connections.lock().unwrap();
+
+    if let Some(conn_state) = connections.get(&connection_id) {
+        match conn_state.client.get_connection() {
+            Ok(mut conn) => {
+                let current_db = conn_state.current_db;
+                if let Err(e) = redis::cmd("SELECT").arg(current_db).query::<()>(&mut conn) {
+                    return Ok(Response::error(format!("切换到数据库 {} 失败: {}", current_db, e)));
+                }
+
+                if target_db == current_db {
+                    return Ok(Response::error("目标数据库与当前数据库相同".to_string()));
+                }
+
+                match redis::cmd("MOVE").arg(&key).arg(target_db).query::<bool>(&mut conn) {
+                    Ok(true) => Ok(Response::<()>::success_empty_with_message(format!("成功移动 {} 到数据库 {}", key, target_db))),
+                    Ok(false) => Ok(Response::error(format!(
+                        "移动失败: 键 {} 不存在或数据库 {} 中已存在同名键",
+                        key, target_db
+                    ))),
+                    Err(e) => Ok(Response::error(format!("移动失败: {}", e))),
+                }
+            }
+            Err(e) => Ok(Response::error(format!("获取连接失败: {}", e))),
+        }
+    } else {
+        Ok(Response::error("Redis 未连接".to_string()))
+    }
+}
+
+// 将匹配模式的键从一个连接迁移到另一个连接（保留 TTL）
+// exact_key 为 true 时 pattern 为完整键名，只迁移该键，键名中的 *、?、[ 等字符不作为通配符
+// 优先使用 DUMP/RESTORE，目标服务器不接受源服务器的 DUMP 格式时按类型重建
+#[tauri::command]
+pub async fn transfer_keys(
+    source_connection_id: String,
+    target_connection_id: String,
+    pattern: String,
+    target_db: Option<u8>, // 为空时使用目标连接的当前库
+    replace: bool,
+    exact_key: bool,
+    state: State<'_, AppState>,
+) -> Result<Response<TransferResult>, String> {
+    // 只复制客户端，迁移期间不占用连接表的锁，其他命令可以照常执行
+    let (source_client, source_db, target_client, target_current_db) = {
+        let connections = state.connections.lock().unwrap();
+        match (
+            connections.get(&source_connection_id),
+            connections.get(&target_connection_id),
+        ) {
+            (Some(source), Some(target)) => (
+                source.client.clone(),
+                source.current_db,
+                target.client.clone(),
+                target.current_db,
+            ),
+            (None, _) => return Ok(Response::error("源连接未连接".to_string())),
+            (_, None) => return Ok(Response::error("目标连接未连接".to_string())),
+        }
+    };
+
+    let target_db = target_db.unwrap_or(target_current_db);
+    if source_connection_id == target_connection_id && target_db == source_db {
+        return Ok(Response::error("源和目标是同一个数据库".to_string()));
+    }
+
+    let mut source = match source_client.get_connection() {
+        Ok(conn) => conn,
+        Err(e) => return Ok(Response::error(format!("获取源连接失败: {}", e))),
+    };
+    let mut target = match target_client.get_connection() {
+        Ok(conn) => conn,
+        Err(e) => return Ok(Response::error(format!("获取目标连接失败: {}", e))),
+    };
+
+    if let Err(e) = redis::cmd("SELECT").arg(source_db).query::<()>(&mut source) {
+        return Ok(Response::error(format!("切换到数据库 {} 失败: {}", source_db, e)));
+    }
+    if let Err(e) = redis::cmd("SELECT").arg(target_db).query::<()>(&mut target) {
+        return Ok(Response::error(format!("切换到数据库 {} 失败: {}", target_db, e)));
+    }
+
+    let mut result = TransferResult {
+        transferred: 0,
+        skipped: Vec::new(),
+        failed: Vec::new(),
+    };
+
+    if exact_key {
+        match redis::cmd("EXISTS").arg(&pattern).query::<bool>(&mut source) {
+            Ok(true) => result.record(&pattern, transfer_one(&mut source, &mut target, &pattern, replace)),
+            Ok(false) => return Ok(Response::error(format!("键 {} 不存在", pattern))),
+            Err(e) => return Ok(Response::error(format!("检查源键失败: {}", e))),
+        }
+        return Ok(result.into_response());
+    }
+
+    // 使用 SCAN 分批迁移，避免 KEYS 阻塞服务器
+    let mut cursor: u64 = 0;
+    loop {
+        let (next, keys): (u64, Vec<String>) = match redis::cmd("SCAN")
+            .arg(cursor)
+            .arg("MATCH")
+            .arg(&pattern)
+            .arg("COUNT")
+            .arg(500)
+            .query(&mut source)
+        {
+            Ok(r) => r,
+            Err(e) => return Ok(Response::error(format!("SCAN命令失败: {}", e))),
+        };
+
+        for key in keys {
+            let outcome = transfer_one(&mut source, &mut target, &key, replace);
+            result.record(&key, outcome);
+        }
+
+        cursor = next;
+        if cursor == 0 {
+            break;
+        }
+    }
+
+    Ok(result.into_response())
+}
+
+enum TransferOutcome {
+    Transferred,
+    Skipped,
+}
+
+impl TransferResult {
+    fn record(&mut self, key: &str, outcome: Result<TransferOutcome, String>) {
+        match outcome {
+            Ok(TransferOutcome::Transferred) => self.transferred += 1,
+            Ok(TransferOutcome::Skipped) => self.skipped.push(key.to_string()),
+            Err(e) => self.failed.push(format!("{}: {}", key, e)),
+        }
+    }
+
+    fn into_response(self) -> Response<TransferResult> {
+        let message = format!(
+            "成功迁移 {} 个键，跳过 {} 个已存在的键，{} 个失败",
+            self.transferred,
+            self.skipped.len(),
+            self.failed.len()
+        );
+        Response::success_with_message(self, message)
+    }
+}
+
+// 读取键的剩余过期毫秒数，没有过期时间时返回 0（RESTORE 的永久约定）；键不存在时返回 None
+pub fn read_pttl(conn: &mut redis::Connection, key: &str) -> Result<Option<i64>, String> {
+    let pttl: i64 = redis::cmd("PTTL")
+        .arg(key)
+        .query(conn)
+        .map_err(|e| format!("获取TTL失败: {}", e))?;
+    match pttl {
+        -2 => Ok(None),
+        -1 => Ok(Some(0)),
+        pttl => Ok(Some(pttl)),
+    }
+}
+
+// 在同一连接上通过 DUMP/RESTORE 复制键，用于不支持 COPY 的旧版本服务器
+fn copy_by_dump(
+    conn: &mut redis::Connection,
+    source_key: &str,
+    target_key: &str,
+    current_db: u8,
+    target_db: u8,
+    replace: bool,
+) -> Result<bool, String> {
+    let payload: Option<Vec<u8>> = redis::cmd("DUMP")
+        .arg(source_key)
+        .query(conn)
+        .map_err(|e| format!("DUMP 失败: {}", e))?;
+    let (Some(payload), Some(pttl)) = (payload, read_pttl(conn, source_key)?) else {
+        return Ok(false);
+    };
+
+    if target_db != current_db {
+        redis::cmd("SELECT")
+            .arg(target_db)
+            .query::<()>(conn)
+            .map_err(|e| format!("切换到数据库 {} 失败: {}", target_db, e))?;
+    }
+
+    let mut cmd = redis::cmd("RESTORE");
+    cmd.arg(target_key).arg(pttl).arg(payload);
+    if replace {
+        cmd.arg("REPLACE");
+    }
+    let restored = match cmd.query::<()>(conn) {
+        Ok(_) => Ok(true),
+        Err(e) if e.code() == Some("BUSYKEY") => Ok(false),
+        Err(e) => Err(format!("RESTORE 失败: {}", e)),
+    };
+
+    if target_db != current_db {
+        let _ = redis::cmd("SELECT").arg(current_db).query::<()>(conn);
+    }
+    restored
+}
+
+// 迁移单个键
+fn transfer_one(
+    source: &mut redis::Connection,
+    target: &mut redis::Connection,
+    key: &str,
+    replace: bool,
+) -> Result<TransferOutcome, String> {
+    if !replace {
+        let exists: bool = redis::cmd("EXISTS")
+            .arg(key)
+            .query(target)
+            .map_err(|e| format!("检查目标键失败: {}", e))?;
+        if exists {
+            return Ok(TransferOutcome::Skipped);
+        }
+    }
+
+    let payload: Option<Vec<u8>> = redis::cmd("DUMP")
+        .arg(key)
+        .query(source)
+        .map_err(|e| format!("DUMP 失败: {}", e))?;
+    // 扫描之后键已过期或被删除，视为跳过
+    let (Some(payload), Some(pttl)) = (payload, read_pttl(source, key)?) else {
+        return Ok(TransferOutcome::Skipped);
+    };
+
+    let mut cmd = redis::cmd("RESTORE");
+    cmd.arg(key).arg(pttl).arg(payload);
+    if replace {
+        cmd.arg("REPLACE");
+    }
+    match cmd.query::<()>(target) {
+        Ok(_) => Ok(TransferOutcome::Transferred),
+        Err(e) if e.code() == Some("BUSYKEY") => Ok(TransferOutcome::Skipped),
+        // 源和目标的 RDB 版本不兼容时按类型重建
+        Err(e) if e.to_string().contains("payload version") => {
+            rebuild_key(source, target, key, pttl).map(|_| TransferOutcome::Transferred)
+        }
+        Err(e) => Err(format!("RESTORE 失败: {}", e)),
+    }
+}
+
+// 读取源键的值并在目标连接上按类型重建，在事务中先删除旧键再写入和设置过期时间
+fn rebuild_key(
+    source: &mut redis::Connection,
+    target: &mut redis::Connection,
+    key: &str,
+    pttl: i64,
+) -> Result<(), String> {
+    let key_type: String = redis::cmd("TYPE")
+        .arg(key)
+        .query(source)
+        .map_err(|e| format!("获取键类型失败: {}", e))?;
+
+    let mut pipe = redis::pipe();
+    pipe.atomic().cmd("DEL").arg(key).ignore();
+    match key_type.as_str() {
+        "string" => {
+            let value: Vec<u8> = redis::cmd("GET").arg(key).query(source).map_err(|e| format!("读取失败: {}", e))?;
+            pipe.cmd("SET").arg(key).arg(value).ignore();
+        }
+        "hash" => {
+            let fields: Vec<(Vec<u8>, Vec<u8>)> = redis::cmd("HGETALL").arg(key).query(source).map_err(|e| format!("读取失败: {}", e))?;
+            pipe.cmd("HSET").arg(key);
+            for (field, value) in fields {
+                pipe.arg(field).arg(value);
+            }
+            pipe.ignore();
+        }
+        "list" => {
+            let items: Vec<Vec<u8>> = redis::cmd("LRANGE").arg(key).arg(0).arg(-1).query(source).map_err(|e| format!("读取失败: {}", e))?;
+            pipe.cmd("RPUSH").arg(key).arg(items).ignore();
+        }
+        "set" => {
+            let members: Vec<Vec<u8>> = redis::cmd("SMEMBERS").arg(key).query(source).map_err(|e| format!("读取失败: {}", e))?;
+            pipe.cmd("SADD").arg(key).arg(members).ignore();
+        }
+        "zset" => {
+            let members: Vec<(Vec<u8>, f64)> = redis::cmd("ZRANGE")
+                .arg(key)
+                .arg(0)
+                .arg(-1)
+                .arg("WITHSCORES")
+                .query(source)
+                .map_err(|e| format!("读取失败: {}", e))?;
+            pipe.cmd("ZADD").arg(key);
+            for (member, score) in members {
+                pipe.arg(score).arg(member);
+            }
+            pipe.ignore();
+        }
+        other => return Err(format!("不支持按类型重建 {} 类型的键", other)),
+    }
+    if pttl > 0 {
+        pipe.cmd("PEXPIRE").arg(key).arg(pttl).ignore();
+    }
+
+    pipe.query::<()>(target).map_err(|e| format!("重建失败: {}", e))
+}
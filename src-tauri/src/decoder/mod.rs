@@ -0,0 +1,330 @@
+mod php;
+pub mod plugin;
+pub mod protobuf;
+
+use base64::Engine;
+use std::io::{Read, Write};
+
+// Protobuf 格式名前缀，完整格式为 "protobuf:<消息类型>"
+pub const PROTOBUF_PREFIX: &str = "protobuf:";
+
+// 外部格式化程序格式名前缀，完整格式为 "plugin:<格式化程序名称>"
+pub const PLUGIN_PREFIX: &str = "plugin:";
+
+// 按 Redis 的 glob 规则匹配键名，支持 *、?、[abc]、[a-z]、[^a] 和 \ 转义
+pub fn match_pattern(pattern: &str, key: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let key: Vec<char> = key.chars().collect();
+    match_chars(&pattern, &key)
+}
+
+fn match_chars(pattern: &[char], key: &[char]) -> bool {
+    match pattern.first() {
+        None => key.is_empty(),
+        Some('*') => (0..=key.len()).any(|i| match_chars(&pattern[1..], &key[i..])),
+        Some('?') => !key.is_empty() && match_chars(&pattern[1..], &key[1..]),
+        Some('[') => {
+            let Some(&c) = key.first() else { return false };
+            let Some(end) = pattern.iter().skip(1).position(|&p| p == ']').map(|i| i + 1) else {
+                return c == '[' && match_chars(&pattern[1..], &key[1..]);
+            };
+            let mut class = &pattern[1..end];
+            let negate = class.first() == Some(&'^');
+            if negate {
+                class = &class[1..];
+            }
+            let mut matched = false;
+            let mut i = 0;
+            while i < class.len() {
+                if i + 2 < class.len() && class[i + 1] == '-' {
+                    matched |= class[i] <= c && c <= class[i + 2];
+                    i += 3;
+                } else {
+                    matched |= class[i] == c;
+                    i += 1;
+                }
+            }
+            matched != negate && match_chars(&pattern[end + 1..], &key[1..])
+        }
+        Some('\\') if pattern.len() > 1 => !key.is_empty() && key[0] == pattern[1] && match_chars(&pattern[2..], &key[1..]),
+        Some(&p) => !key.is_empty() && key[0] == p && match_chars(&pattern[1..], &key[1..]),
+    }
+}
+
+// 内置值格式
+// 压缩和 base64 类格式解码后得到原始字节，再按文本展示（内容为 JSON 时自动格式化）
+// MessagePack / CBOR / PHP 等结构化格式解码为 JSON 展示
+// hex 与其他格式方向相反：把任意二进制值展示为十六进制文本
+pub const BUILTIN_FORMATS: &[&str] = &[
+    "text", "json", "gzip", "zlib", "zstd", "lz4", "msgpack", "cbor", "base64", "hex", "php",
+];
+
+// 将原始字节按指定格式解码为可展示的文本
+pub fn decode(format: &str, bytes: &[u8]) -> Result<String, String> {
+    match format {
+        "text" => String::from_utf8(bytes.to_vec()).map_err(|_| "值不是有效的 UTF-8 文本".to_string()),
+        "json" => {
+            let value: serde_json::Value = serde_json::from_slice(bytes).map_err(|e| format!("JSON 解析失败: {}", e))?;
+            serde_json::to_string_pretty(&value).map_err(|e| e.to_string())
+        }
+        "gzip" => {
+            let mut out = Vec::new();
+            flate2::read::GzDecoder::new(bytes)
+                .read_to_end(&mut out)
+                .map_err(|e| format!("gzip 解压失败: {}", e))?;
+            render_text(out)
+        }
+        "zlib" => {
+            let mut out = Vec::new();
+            flate2::read::ZlibDecoder::new(bytes)
+                .read_to_end(&mut out)
+                .map_err(|e| format!("zlib 解压失败: {}", e))?;
+            render_text(out)
+        }
+        "zstd" => {
+            let out = zstd::decode_all(bytes).map_err(|e| format!("zstd 解压失败: {}", e))?;
+            render_text(out)
+        }
+        "lz4" => {
+            let mut out = Vec::new();
+            lz4_flex::frame::FrameDecoder::new(bytes)
+                .read_to_end(&mut out)
+                .map_err(|e| format!("lz4 解压失败: {}", e))?;
+            render_text(out)
+        }
+        "msgpack" => {
+            let value = decode_msgpack(bytes)?;
+            serde_json::to_string_pretty(&value).map_err(|e| e.to_string())
+        }
+        "cbor" => {
+            let value = decode_cbor(bytes)?;
+            serde_json::to_string_pretty(&value).map_err(|e| e.to_string())
+        }
+        "base64" => {
+            let trimmed: Vec<u8> = bytes.iter().copied().filter(|b| !b.is_ascii_whitespace()).collect();
+            let out = base64::engine::general_purpose::STANDARD
+                .decode(trimmed)
+                .map_err(|e| format!("base64 解码失败: {}", e))?;
+            render_text(out)
+        }
+        "hex" => Ok(hex::encode(bytes)),
+        "php" => {
+            let value = php::unserialize(bytes)?;
+            serde_json::to_string_pretty(&value).map_err(|e| e.to_string())
+        }
+        _ => Err(format!("不支持的格式: {}", format)),
+    }
+}
+
+// 将编辑后的文本按指定格式重新编码为原始字节
+pub fn encode(format: &str, text: &str) -> Result<Vec<u8>, String> {
+    match format {
+        "text" => Ok(text.as_bytes().to_vec()),
+        "json" => {
+            let value = parse_json(text)?;
+            serde_json::to_vec(&value).map_err(|e| e.to_string())
+        }
+        "gzip" => {
+            let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+            encoder.write_all(&compact_text(text)).map_err(|e| format!("gzip 压缩失败: {}", e))?;
+            encoder.finish().map_err(|e| format!("gzip 压缩失败: {}", e))
+        }
+        "zlib" => {
+            let mut encoder = flate2::write::ZlibEncoder::new(Vec::new(), flate2::Compression::default());
+            encoder.write_all(&compact_text(text)).map_err(|e| format!("zlib 压缩失败: {}", e))?;
+            encoder.finish().map_err(|e| format!("zlib 压缩失败: {}", e))
+        }
+        "zstd" => zstd::encode_all(compact_text(text).as_slice(), 0).map_err(|e| format!("zstd 压缩失败: {}", e)),
+        "lz4" => {
+            let mut encoder = lz4_flex::frame::FrameEncoder::new(Vec::new());
+            encoder.write_all(&compact_text(text)).map_err(|e| format!("lz4 压缩失败: {}", e))?;
+            encoder.finish().map_err(|e| format!("lz4 压缩失败: {}", e))
+        }
+        "msgpack" => {
+            let value = parse_json(text)?;
+            rmp_serde::to_vec(&value).map_err(|e| format!("MessagePack 编码失败: {}", e))
+        }
+        "cbor" => {
+            let value = parse_json(text)?;
+            let mut out = Vec::new();
+            ciborium::into_writer(&value, &mut out).map_err(|e| format!("CBOR 编码失败: {}", e))?;
+            Ok(out)
+        }
+        "base64" => Ok(base64::engine::general_purpose::STANDARD
+            .encode(compact_text(text))
+            .into_bytes()),
+        "hex" => {
+            let trimmed: String = text.chars().filter(|c| !c.is_whitespace()).collect();
+            hex::decode(trimmed).map_err(|e| format!("十六进制解析失败: {}", e))
+        }
+        "php" => {
+            let value = parse_json(text)?;
+            php::serialize(&value)
+        }
+        _ => Err(format!("不支持的格式: {}", format)),
+    }
+}
+
+// 根据魔数和内容特征自动识别格式，无法识别时按是否为 UTF-8 回退到 text 或 hex
+pub fn detect(bytes: &[u8]) -> &'static str {
+    if bytes.starts_with(&[0x1f, 0x8b]) {
+        return "gzip";
+    }
+    if bytes.starts_with(&[0x28, 0xb5, 0x2f, 0xfd]) {
+        return "zstd";
+    }
+    if bytes.starts_with(&[0x04, 0x22, 0x4d, 0x18]) {
+        return "lz4";
+    }
+    if is_zlib_header(bytes) && decode("zlib", bytes).is_ok() {
+        return "zlib";
+    }
+
+    if let Some(first) = bytes.first() {
+        match first {
+            b'{' | b'[' if serde_json::from_slice::<serde_json::Value>(bytes).is_ok() => return "json",
+            b'a' | b'O' | b's' | b'i' | b'b' | b'd' | b'N' if php::unserialize(bytes).is_ok() => return "php",
+            // MessagePack 的 map / array 头
+            0x80..=0x9f | 0xdc..=0xdf if decode_msgpack(bytes).is_ok() => return "msgpack",
+            // CBOR 的 map 头、不定长 array 头和自描述标签 0xd9d9f7
+            0xa0..=0xbf | 0x9f | 0xd9 if decode_cbor(bytes).is_ok() => return "cbor",
+            _ => {}
+        }
+    }
+
+    if looks_like_base64(bytes) {
+        return "base64";
+    }
+
+    if std::str::from_utf8(bytes).is_ok() {
+        "text"
+    } else {
+        "hex"
+    }
+}
+
+// zlib 头：CMF 低 4 位为 8（deflate），且 CMF*256+FLG 能被 31 整除
+fn is_zlib_header(bytes: &[u8]) -> bool {
+    bytes.len() >= 2 && bytes[0] & 0x0f == 8 && (u16::from(bytes[0]) * 256 + u16::from(bytes[1])).is_multiple_of(31)
+}
+
+// 只有解码结果本身是可读文本时才认为是 base64，避免把普通单词误判为 base64
+fn looks_like_base64(bytes: &[u8]) -> bool {
+    if bytes.len() < 8 || !bytes.len().is_multiple_of(4) {
+        return false;
+    }
+    if !bytes.iter().all(|b| b.is_ascii_alphanumeric() || matches!(b, b'+' | b'/' | b'=')) {
+        return false;
+    }
+    match base64::engine::general_purpose::STANDARD.decode(bytes) {
+        Ok(decoded) => detect(&decoded) != "hex",
+        Err(_) => false,
+    }
+}
+
+// 解码 MessagePack，要求数据被完整消费
+fn decode_msgpack(bytes: &[u8]) -> Result<serde_json::Value, String> {
+    let mut cursor = std::io::Cursor::new(bytes);
+    let value: serde_json::Value = rmp_serde::from_read(&mut cursor).map_err(|e| format!("MessagePack 解析失败: {}", e))?;
+    if cursor.position() as usize != bytes.len() {
+        return Err("MessagePack 数据后有多余内容".to_string());
+    }
+    Ok(value)
+}
+
+// 解码 CBOR，要求数据被完整消费
+fn decode_cbor(bytes: &[u8]) -> Result<serde_json::Value, String> {
+    let mut reader = bytes;
+    let value: serde_json::Value = ciborium::from_reader(&mut reader).map_err(|e| format!("CBOR 解析失败: {}", e))?;
+    if !reader.is_empty() {
+        return Err("CBOR 数据后有多余内容".to_string());
+    }
+    Ok(value)
+}
+
+fn parse_json(text: &str) -> Result<serde_json::Value, String> {
+    serde_json::from_str(text).map_err(|e| format!("JSON 解析失败: {}", e))
+}
+
+// 解压/解码后的字节按文本展示，内容为 JSON 时格式化
+fn render_text(bytes: Vec<u8>) -> Result<String, String> {
+    let text = String::from_utf8(bytes).map_err(|_| "解码后的内容不是有效的 UTF-8 文本".to_string())?;
+    match serde_json::from_str::<serde_json::Value>(&text) {
+        Ok(value) if text.trim_start().starts_with(['{', '[']) => serde_json::to_string_pretty(&value).map_err(|e| e.to_string()),
+        _ => Ok(text),
+    }
+}
+
+// 与 render_text 相反：展示时格式化过的 JSON 在保存前压缩回紧凑形式
+fn compact_text(text: &str) -> Vec<u8> {
+    match serde_json::from_str::<serde_json::Value>(text) {
+        Ok(value) if text.trim_start().starts_with(['{', '[']) => serde_json::to_vec(&value).unwrap_or_else(|_| text.as_bytes().to_vec()),
+        _ => text.as_bytes().to_vec(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn match_pattern_follows_redis_glob() {
+        assert!(match_pattern("user:*", "user:1"));
+        assert!(match_pattern("user:*", "user:"));
+        assert!(!match_pattern("user:*", "order:1"));
+        assert!(match_pattern("h?llo", "hello"));
+        assert!(!match_pattern("h?llo", "hllo"));
+        assert!(match_pattern("h[ae]llo", "hallo"));
+        assert!(!match_pattern("h[ae]llo", "hillo"));
+        assert!(match_pattern("h[^e]llo", "hallo"));
+        assert!(!match_pattern("h[^e]llo", "hello"));
+        assert!(match_pattern("v[0-9]", "v7"));
+        assert!(match_pattern("a\\*b", "a*b"));
+        assert!(!match_pattern("a\\*b", "axb"));
+        assert!(match_pattern("订单:*", "订单:42"));
+    }
+
+    #[test]
+    fn text_formats_round_trip() {
+        let text = "{\n  \"id\": 1,\n  \"name\": \"中文\"\n}";
+        for format in ["gzip", "zlib", "zstd", "lz4", "base64", "msgpack", "cbor", "php"] {
+            let encoded = encode(format, text).unwrap();
+            let decoded = decode(format, &encoded).unwrap();
+            assert_eq!(decoded, text, "format {}", format);
+        }
+    }
+
+    #[test]
+    fn structured_formats_keep_key_order() {
+        let text = "{\n  \"name\": \"bob\",\n  \"id\": 1,\n  \"meta\": {\n    \"z\": true,\n    \"a\": null\n  }\n}";
+        for format in ["json", "msgpack", "cbor", "php"] {
+            let encoded = encode(format, text).unwrap();
+            let decoded = decode(format, &encoded).unwrap();
+            assert_eq!(decoded, text, "format {}", format);
+        }
+    }
+
+    #[test]
+    fn binary_formats_round_trip() {
+        let bytes = [0u8, 159, 146, 150, 255];
+        let text = decode("hex", &bytes).unwrap();
+        assert_eq!(text, "009f9296ff");
+        assert_eq!(encode("hex", &text).unwrap(), bytes);
+
+        let php = b"a:1:{i:0;s:2:\"\xff\xfe\";}";
+        let text = decode("php", php).unwrap();
+        assert_eq!(encode("php", &text).unwrap(), php);
+    }
+
+    #[test]
+    fn detect_recognizes_encoded_values() {
+        let json = "{\"id\": 1}";
+        for format in ["gzip", "zlib", "zstd", "lz4", "msgpack", "cbor", "php", "base64"] {
+            let encoded = encode(format, json).unwrap();
+            assert_eq!(detect(&encoded), format, "format {}", format);
+        }
+        assert_eq!(detect(json.as_bytes()), "json");
+        assert_eq!(detect(b"hello"), "text");
+        assert_eq!(detect(&[0xff, 0x00, 0x01]), "hex");
+    }
+}
@@ -0,0 +1,343 @@
+use base64::Engine;
+use serde_json::{Map, Number, Value};
+
+// PHP 对象反序列化后用该字段保存类名，序列化时据此还原为 O: 格式
+const CLASS_FIELD: &str = "__class";
+
+// 不是有效 UTF-8 的字符串值解码为 {"__bytes": "<base64>"}，序列化时还原为原始字节，保证往返不改变数据
+const BYTES_FIELD: &str = "__bytes";
+
+// 将 PHP serialize() 的结果解析为 JSON
+// 连续整数下标（0..n）的数组转为 JSON 数组，其余数组和对象转为 JSON 对象
+pub fn unserialize(bytes: &[u8]) -> Result<Value, String> {
+    let mut parser = Parser { bytes, pos: 0 };
+    let value = parser.parse_value()?;
+    if parser.pos != bytes.len() {
+        return Err(format!("PHP 序列化数据在位置 {} 后有多余内容", parser.pos));
+    }
+    Ok(value)
+}
+
+// 将 JSON 序列化为 PHP serialize() 格式
+pub fn serialize(value: &Value) -> Result<Vec<u8>, String> {
+    let mut out = Vec::new();
+    write_value(&mut out, value)?;
+    Ok(out)
+}
+
+struct Parser<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn peek(&self) -> Option<u8> {
+        self.bytes.get(self.pos).copied()
+    }
+
+    fn expect(&mut self, expected: u8) -> Result<(), String> {
+        match self.peek() {
+            Some(b) if b == expected => {
+                self.pos += 1;
+                Ok(())
+            }
+            _ => Err(format!("PHP 序列化数据在位置 {} 处应为 '{}'", self.pos, expected as char)),
+        }
+    }
+
+    // 读取到指定分隔符为止的内容（不含分隔符）
+    fn read_until(&mut self, delimiter: u8) -> Result<&'a str, String> {
+        let start = self.pos;
+        while let Some(b) = self.peek() {
+            if b == delimiter {
+                let raw = &self.bytes[start..self.pos];
+                self.pos += 1;
+                return std::str::from_utf8(raw).map_err(|e| format!("PHP 序列化数据无效: {}", e));
+            }
+            self.pos += 1;
+        }
+        Err(format!("PHP 序列化数据意外结束，缺少 '{}'", delimiter as char))
+    }
+
+    fn read_length(&mut self) -> Result<usize, String> {
+        self.read_until(b':')?
+            .parse::<usize>()
+            .map_err(|e| format!("PHP 序列化长度无效: {}", e))
+    }
+
+    // 读取 "LEN:"...":" 形式的字符串内容，LEN 为字节数
+    fn read_quoted(&mut self) -> Result<&'a [u8], String> {
+        let len = self.read_length()?;
+        self.expect(b'"')?;
+        let end = self.pos + len;
+        if end > self.bytes.len() {
+            return Err("PHP 序列化字符串长度超出数据范围".to_string());
+        }
+        let raw = &self.bytes[self.pos..end];
+        self.pos = end;
+        self.expect(b'"')?;
+        Ok(raw)
+    }
+
+    // 类名和数组键必须是文本，包含非 UTF-8 字节时拒绝解码，避免保存时写回错误的数据
+    fn read_quoted_str(&mut self) -> Result<String, String> {
+        let start = self.pos;
+        let raw = self.read_quoted()?;
+        std::str::from_utf8(raw)
+            .map(|s| s.to_string())
+            .map_err(|_| format!("PHP 序列化数据在位置 {} 处的类名或键不是有效的 UTF-8 文本", start))
+    }
+
+    fn parse_value(&mut self) -> Result<Value, String> {
+        let tag = self.peek().ok_or("PHP 序列化数据意外结束")?;
+        self.pos += 1;
+
+        match tag {
+            b'N' => {
+                self.expect(b';')?;
+                Ok(Value::Null)
+            }
+            b'b' => {
+                self.expect(b':')?;
+                let raw = self.read_until(b';')?;
+                Ok(Value::Bool(raw == "1"))
+            }
+            b'i' => {
+                self.expect(b':')?;
+                let raw = self.read_until(b';')?;
+                raw.parse::<i64>()
+                    .map(|n| Value::Number(n.into()))
+                    .map_err(|e| format!("PHP 整数无效: {}", e))
+            }
+            b'd' => {
+                self.expect(b':')?;
+                let raw = self.read_until(b';')?;
+                let n = raw.parse::<f64>().map_err(|e| format!("PHP 浮点数无效: {}", e))?;
+                // INF / NAN 无法用 JSON 表示，保留原始文本
+                Ok(Number::from_f64(n).map(Value::Number).unwrap_or_else(|| Value::String(raw.to_string())))
+            }
+            b's' => {
+                self.expect(b':')?;
+                let raw = self.read_quoted()?;
+                self.expect(b';')?;
+                Ok(match std::str::from_utf8(raw) {
+                    Ok(s) => Value::String(s.to_string()),
+                    Err(_) => bytes_to_value(raw),
+                })
+            }
+            b'a' => {
+                self.expect(b':')?;
+                let entries = self.parse_entries()?;
+                Ok(entries_to_value(entries))
+            }
+            b'O' => {
+                self.expect(b':')?;
+                let class = self.read_quoted_str()?;
+                self.expect(b':')?;
+                let entries = self.parse_entries()?;
+                let mut map = Map::new();
+                map.insert(CLASS_FIELD.to_string(), Value::String(class));
+                for (k, v) in entries {
+                    map.insert(key_to_string(k), v);
+                }
+                Ok(Value::Object(map))
+            }
+            _ => Err(format!("不支持的 PHP 序列化类型 '{}'", tag as char)),
+        }
+    }
+
+    // 解析 "N:{key;value...}" 形式的键值对列表
+    fn parse_entries(&mut self) -> Result<Vec<(Value, Value)>, String> {
+        let count = self.read_length()?;
+        self.expect(b'{')?;
+        let mut entries = Vec::with_capacity(count);
+        for _ in 0..count {
+            let key = self.parse_value()?;
+            if !key.is_i64() && !key.is_string() {
+                return Err("PHP 数组的键只能是整数或 UTF-8 字符串".to_string());
+            }
+            let value = self.parse_value()?;
+            entries.push((key, value));
+        }
+        self.expect(b'}')?;
+        Ok(entries)
+    }
+}
+
+fn key_to_string(key: Value) -> String {
+    match key {
+        Value::String(s) => s,
+        other => other.to_string(),
+    }
+}
+
+fn entries_to_value(entries: Vec<(Value, Value)>) -> Value {
+    let is_list = entries
+        .iter()
+        .enumerate()
+        .all(|(i, (k, _))| k.as_i64() == Some(i as i64));
+
+    if is_list {
+        Value::Array(entries.into_iter().map(|(_, v)| v).collect())
+    } else {
+        Value::Object(entries.into_iter().map(|(k, v)| (key_to_string(k), v)).collect())
+    }
+}
+
+fn bytes_to_value(bytes: &[u8]) -> Value {
+    let mut map = Map::new();
+    map.insert(
+        BYTES_FIELD.to_string(),
+        Value::String(base64::engine::general_purpose::STANDARD.encode(bytes)),
+    );
+    Value::Object(map)
+}
+
+// 识别 {"__bytes": "<base64>"}，返回其中的原始字节
+fn value_to_bytes(map: &Map<String, Value>) -> Option<Result<Vec<u8>, String>> {
+    if map.len() != 1 {
+        return None;
+    }
+    let encoded = map.get(BYTES_FIELD)?.as_str()?;
+    Some(
+        base64::engine::general_purpose::STANDARD
+            .decode(encoded)
+            .map_err(|e| format!("{} 字段不是有效的 base64: {}", BYTES_FIELD, e)),
+    )
+}
+
+fn write_bytes(out: &mut Vec<u8>, bytes: &[u8]) {
+    out.extend_from_slice(format!("s:{}:\"", bytes.len()).as_bytes());
+    out.extend_from_slice(bytes);
+    out.extend_from_slice(b"\";");
+}
+
+fn write_string(out: &mut Vec<u8>, s: &str) {
+    write_bytes(out, s.as_bytes());
+}
+
+// 数组键：可解析为整数的键按整数写入，与 PHP 自身的行为一致
+fn write_key(out: &mut Vec<u8>, key: &str) {
+    match key.parse::<i64>() {
+        Ok(n) if n.to_string() == key => out.extend_from_slice(format!("i:{};", n).as_bytes()),
+        _ => write_string(out, key),
+    }
+}
+
+fn write_value(out: &mut Vec<u8>, value: &Value) -> Result<(), String> {
+    match value {
+        Value::Null => out.extend_from_slice(b"N;"),
+        Value::Bool(b) => out.extend_from_slice(if *b { b"b:1;" } else { b"b:0;" }),
+        Value::Number(n) => {
+            if let Some(i) = n.as_i64() {
+                out.extend_from_slice(format!("i:{};", i).as_bytes());
+            } else if let Some(f) = n.as_f64() {
+                out.extend_from_slice(format!("d:{};", f).as_bytes());
+            } else {
+                return Err(format!("无法序列化数字 {}", n));
+            }
+        }
+        Value::String(s) => write_string(out, s),
+        Value::Array(items) => {
+            out.extend_from_slice(format!("a:{}:{{", items.len()).as_bytes());
+            for (i, item) in items.iter().enumerate() {
+                out.extend_from_slice(format!("i:{};", i).as_bytes());
+                write_value(out, item)?;
+            }
+            out.push(b'}');
+        }
+        Value::Object(map) => {
+            if let Some(bytes) = value_to_bytes(map) {
+                write_bytes(out, &bytes?);
+                return Ok(());
+            }
+            let class = map.get(CLASS_FIELD).and_then(|c| c.as_str());
+            let fields: Vec<(&String, &Value)> = map.iter().filter(|(k, _)| class.is_none() || k.as_str() != CLASS_FIELD).collect();
+            match class {
+                Some(class) => {
+                    out.extend_from_slice(format!("O:{}:\"{}\":{}:{{", class.len(), class, fields.len()).as_bytes());
+                    for (k, v) in fields {
+                        write_string(out, k);
+                        write_value(out, v)?;
+                    }
+                }
+                None => {
+                    out.extend_from_slice(format!("a:{}:{{", fields.len()).as_bytes());
+                    for (k, v) in fields {
+                        write_key(out, k);
+                        write_value(out, v)?;
+                    }
+                }
+            }
+            out.push(b'}');
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn round_trip(bytes: &[u8]) {
+        let value = unserialize(bytes).unwrap();
+        assert_eq!(serialize(&value).unwrap(), bytes);
+    }
+
+    #[test]
+    fn unserialize_scalars() {
+        assert_eq!(unserialize(b"N;").unwrap(), Value::Null);
+        assert_eq!(unserialize(b"b:1;").unwrap(), json!(true));
+        assert_eq!(unserialize(b"i:-42;").unwrap(), json!(-42));
+        assert_eq!(unserialize(b"d:1.5;").unwrap(), json!(1.5));
+        assert_eq!(unserialize("s:6:\"中文\";".as_bytes()).unwrap(), json!("中文"));
+    }
+
+    #[test]
+    fn unserialize_arrays_and_objects() {
+        let list = unserialize(b"a:2:{i:0;s:1:\"a\";i:1;s:1:\"b\";}").unwrap();
+        assert_eq!(list, json!(["a", "b"]));
+
+        let map = unserialize(b"a:2:{s:4:\"name\";s:3:\"bob\";i:5;b:0;}").unwrap();
+        assert_eq!(map, json!({"name": "bob", "5": false}));
+
+        let object = unserialize(b"O:4:\"User\":1:{s:2:\"id\";i:7;}").unwrap();
+        assert_eq!(object, json!({"__class": "User", "id": 7}));
+    }
+
+    #[test]
+    fn round_trips_preserve_bytes() {
+        round_trip(b"a:2:{i:0;s:1:\"a\";i:1;a:1:{s:1:\"k\";N;}}");
+        round_trip(b"O:4:\"User\":2:{s:2:\"id\";i:7;s:4:\"tags\";a:0:{}}");
+        round_trip("a:1:{s:4:\"name\";s:6:\"中文\";}".as_bytes());
+    }
+
+    #[test]
+    fn round_trips_keep_key_order() {
+        round_trip(b"a:2:{s:4:\"name\";s:3:\"bob\";i:5;b:0;}");
+        round_trip(b"a:3:{s:1:\"z\";i:1;s:1:\"a\";i:2;s:1:\"m\";a:2:{s:1:\"y\";N;s:1:\"b\";N;}}");
+        round_trip(b"O:4:\"User\":2:{s:4:\"name\";s:3:\"bob\";s:2:\"id\";i:7;}");
+    }
+
+    #[test]
+    fn binary_strings_round_trip() {
+        let bytes = b"a:1:{s:3:\"raw\";s:3:\"\xff\x00\xfe\";}";
+        let value = unserialize(bytes).unwrap();
+        assert_eq!(value, json!({"raw": {"__bytes": "/wD+"}}));
+        assert_eq!(serialize(&value).unwrap(), bytes);
+    }
+
+    #[test]
+    fn binary_keys_are_rejected() {
+        assert!(unserialize(b"a:1:{s:1:\"\xff\";i:1;}").is_err());
+        assert!(unserialize(b"O:1:\"\xff\":0:{}").is_err());
+    }
+
+    #[test]
+    fn malformed_input_is_rejected() {
+        assert!(unserialize(b"s:5:\"abc\";").is_err());
+        assert!(unserialize(b"i:1;extra").is_err());
+        assert!(unserialize(b"x:1;").is_err());
+    }
+}
@@ -0,0 +1,168 @@
+use super::match_pattern;
+use serde::{Deserialize, Serialize};
+use std::io::{Read, Write};
+use std::process::{Command, Stdio};
+use std::sync::mpsc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+fn default_timeout_ms() -> u64 {
+    5000
+}
+
+// 进程退出后等待输出读取完成的最短时间
+const OUTPUT_GRACE: Duration = Duration::from_millis(100);
+
+// 外部格式化程序：解码时原始字节写入 stdin，从 stdout 读取展示文本；编码时方向相反
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FormatterPlugin {
+    pub name: String,
+    pub decode_command: String,
+    #[serde(default)]
+    pub decode_args: Vec<String>,
+    pub encode_command: Option<String>, // 为空时该格式只读
+    #[serde(default)]
+    pub encode_args: Vec<String>,
+    #[serde(default)]
+    pub patterns: Vec<String>, // 自动识别时按键名匹配的模式
+    #[serde(default = "default_timeout_ms")]
+    pub timeout_ms: u64,
+}
+
+impl FormatterPlugin {
+    pub fn matches(&self, key: &str) -> bool {
+        self.patterns.iter().any(|p| match_pattern(p, key))
+    }
+
+    pub fn decode(&self, bytes: &[u8]) -> Result<String, String> {
+        let out = run(&self.decode_command, &self.decode_args, bytes, self.timeout_ms)
+            .map_err(|e| format!("格式化程序 {} 解码失败: {}", self.name, e))?;
+        String::from_utf8(out).map_err(|_| format!("格式化程序 {} 的输出不是有效的 UTF-8 文本", self.name))
+    }
+
+    pub fn encode(&self, text: &str) -> Result<Vec<u8>, String> {
+        let command = self
+            .encode_command
+            .as_deref()
+            .filter(|c| !c.trim().is_empty())
+            .ok_or_else(|| format!("格式化程序 {} 未配置编码命令", self.name))?;
+        run(command, &self.encode_args, text.as_bytes(), self.timeout_ms)
+            .map_err(|e| format!("格式化程序 {} 编码失败: {}", self.name, e))
+    }
+}
+
+// 执行外部命令，超时后结束进程；非零退出码时返回 stderr 内容
+fn run(command: &str, args: &[String], input: &[u8], timeout_ms: u64) -> Result<Vec<u8>, String> {
+    let mut child = Command::new(command)
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("启动 {} 失败: {}", command, e))?;
+
+    // stdin / stdout / stderr 分别在独立线程中读写，避免管道缓冲区写满导致互相等待
+    // 程序可能不读取 stdin 就直接退出，此时写入失败不视为错误，也不等待写入线程
+    let mut stdin = child.stdin.take().ok_or("无法打开标准输入")?;
+    let input = input.to_vec();
+    thread::spawn(move || stdin.write_all(&input));
+
+    // 输出通过通道返回，按截止时间等待：程序启动的子进程可能继承并一直占用输出管道
+    let mut stdout = child.stdout.take().ok_or("无法打开标准输出")?;
+    let (stdout_tx, stdout_rx) = mpsc::channel();
+    thread::spawn(move || {
+        let mut buf = Vec::new();
+        let _ = stdout_tx.send(stdout.read_to_end(&mut buf).map(|_| buf));
+    });
+
+    let mut stderr = child.stderr.take().ok_or("无法打开标准错误输出")?;
+    let (stderr_tx, stderr_rx) = mpsc::channel();
+    thread::spawn(move || {
+        let mut buf = Vec::new();
+        let _ = stderr.read_to_end(&mut buf);
+        let _ = stderr_tx.send(buf);
+    });
+
+    let deadline = Instant::now() + Duration::from_millis(timeout_ms);
+    let status = loop {
+        match child.try_wait() {
+            Ok(Some(status)) => break status,
+            Ok(None) if Instant::now() >= deadline => {
+                let _ = child.kill();
+                let _ = child.wait();
+                return Err(format!("执行超时（{} 毫秒）", timeout_ms));
+            }
+            Ok(None) => thread::sleep(Duration::from_millis(10)),
+            Err(e) => return Err(format!("等待进程结束失败: {}", e)),
+        }
+    };
+
+    // 进程恰好在截止时间前退出时，留出少量时间读完管道中剩余的输出
+    let remaining = || deadline.saturating_duration_since(Instant::now()).max(OUTPUT_GRACE);
+    let stdout = match stdout_rx.recv_timeout(remaining()) {
+        Ok(result) => result.map_err(|e| format!("读取标准输出失败: {}", e))?,
+        Err(_) => return Err(format!("执行超时（{} 毫秒），标准输出仍被子进程占用", timeout_ms)),
+    };
+    let stderr = stderr_rx.recv_timeout(remaining()).unwrap_or_default();
+
+    if !status.success() {
+        let message = String::from_utf8_lossy(&stderr).trim().to_string();
+        return Err(match status.code() {
+            Some(code) if message.is_empty() => format!("退出码 {}", code),
+            Some(code) => format!("退出码 {}: {}", code, message),
+            None if message.is_empty() => "进程被信号终止".to_string(),
+            None => format!("进程被信号终止: {}", message),
+        });
+    }
+
+    Ok(stdout)
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    fn shell(script: &str) -> FormatterPlugin {
+        FormatterPlugin {
+            name: "test".to_string(),
+            decode_command: "sh".to_string(),
+            decode_args: vec!["-c".to_string(), script.to_string()],
+            encode_command: None,
+            encode_args: Vec::new(),
+            patterns: vec!["user:*".to_string()],
+            timeout_ms: 500,
+        }
+    }
+
+    #[test]
+    fn decode_pipes_value_through_command() {
+        assert_eq!(shell("tr a-z A-Z").decode(b"hello").unwrap(), "HELLO");
+    }
+
+    #[test]
+    fn non_zero_exit_reports_stderr() {
+        let err = shell("echo broken >&2; exit 3").decode(b"").unwrap_err();
+        assert!(err.contains("退出码 3: broken"), "{}", err);
+    }
+
+    #[test]
+    fn slow_command_times_out() {
+        let started = Instant::now();
+        assert!(shell("sleep 5").decode(b"").is_err());
+        assert!(started.elapsed() < Duration::from_secs(2));
+    }
+
+    #[test]
+    fn grandchild_holding_stdout_does_not_hang() {
+        let started = Instant::now();
+        assert!(shell("sleep 5 & echo done").decode(b"").is_err());
+        assert!(started.elapsed() < Duration::from_secs(2));
+    }
+
+    #[test]
+    fn encode_requires_command() {
+        assert!(shell("cat").encode("x").is_err());
+        assert!(shell("cat").matches("user:1"));
+        assert!(!shell("cat").matches("order:1"));
+    }
+}
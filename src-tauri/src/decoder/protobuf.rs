@@ -0,0 +1,101 @@
+use super::match_pattern;
+use prost::Message;
+use prost_reflect::{DescriptorPool, DynamicMessage, MessageDescriptor};
+use prost_types::FileDescriptorSet;
+use serde::{Deserialize, Serialize};
+
+// 键模式到消息类型的映射
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProtoMapping {
+    pub pattern: String,
+    pub message_type: String, // 完整消息名，例如 "shop.v1.Order"
+}
+
+// 每个连接独立的 Protobuf 描述符和映射
+#[derive(Debug, Default)]
+pub struct ProtoRegistry {
+    pool: Option<DescriptorPool>,
+    mappings: Vec<ProtoMapping>,
+}
+
+// 编译 .proto 文件，耗时较长，调用方应在持有连接锁之前完成
+pub fn compile_proto_files(files: &[String], include_paths: &[String]) -> Result<FileDescriptorSet, String> {
+    protox::compile(files, include_paths).map_err(|e| format!("编译 proto 文件失败: {}", e))
+}
+
+impl ProtoRegistry {
+    // 将编译好的 .proto 文件合并到描述符池
+    pub fn add_file_descriptor_set(&mut self, file_set: FileDescriptorSet) -> Result<(), String> {
+        self.pool_mut()
+            .add_file_descriptor_set(file_set)
+            .map_err(|e| format!("加载描述符失败: {}", e))
+    }
+
+    // 加载 protoc --descriptor_set_out 生成的 FileDescriptorSet 并合并到描述符池
+    pub fn add_descriptor_set(&mut self, bytes: &[u8]) -> Result<(), String> {
+        self.pool_mut()
+            .decode_file_descriptor_set(bytes)
+            .map_err(|e| format!("解析 FileDescriptorSet 失败: {}", e))
+    }
+
+    pub fn set_mappings(&mut self, mappings: Vec<ProtoMapping>) -> Result<(), String> {
+        for mapping in &mappings {
+            self.message(&mapping.message_type)?;
+        }
+        self.mappings = mappings;
+        Ok(())
+    }
+
+    pub fn mappings(&self) -> &[ProtoMapping] {
+        &self.mappings
+    }
+
+    // 已注册的全部消息类型
+    pub fn message_types(&self) -> Vec<String> {
+        self.pool
+            .as_ref()
+            .map(|pool| pool.all_messages().map(|m| m.full_name().to_string()).collect())
+            .unwrap_or_default()
+    }
+
+    pub fn clear(&mut self) {
+        self.pool = None;
+        self.mappings.clear();
+    }
+
+    // 按映射顺序查找第一个匹配键名的消息类型
+    pub fn message_type_for_key(&self, key: &str) -> Option<&str> {
+        self.mappings
+            .iter()
+            .find(|m| match_pattern(&m.pattern, key))
+            .map(|m| m.message_type.as_str())
+    }
+
+    // 将 Protobuf 字节解码为 JSON
+    pub fn decode(&self, message_type: &str, bytes: &[u8]) -> Result<serde_json::Value, String> {
+        let descriptor = self.message(message_type)?;
+        let message = DynamicMessage::decode(descriptor, bytes).map_err(|e| format!("Protobuf 解码失败: {}", e))?;
+        serde_json::to_value(&message).map_err(|e| format!("Protobuf 转换 JSON 失败: {}", e))
+    }
+
+    // 将 JSON 文本编码为 Protobuf 字节
+    pub fn encode(&self, message_type: &str, text: &str) -> Result<Vec<u8>, String> {
+        let descriptor = self.message(message_type)?;
+        let mut deserializer = serde_json::Deserializer::from_str(text);
+        let message = DynamicMessage::deserialize(descriptor, &mut deserializer)
+            .map_err(|e| format!("JSON 不符合消息类型 {}: {}", message_type, e))?;
+        deserializer.end().map_err(|e| format!("JSON 解析失败: {}", e))?;
+        Ok(message.encode_to_vec())
+    }
+
+    fn message(&self, message_type: &str) -> Result<MessageDescriptor, String> {
+        self.pool
+            .as_ref()
+            .and_then(|pool| pool.get_message_by_name(message_type))
+            .ok_or_else(|| format!("未注册的消息类型: {}", message_type))
+    }
+
+    fn pool_mut(&mut self) -> &mut DescriptorPool {
+        self.pool.get_or_insert_with(DescriptorPool::new)
+    }
+}
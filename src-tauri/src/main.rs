@@ -1,6 +1,7 @@
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
 mod commands;
+mod decoder;
 mod state;
 
 use state::AppState;
@@ -8,7 +9,12 @@ use state::AppState;
 fn main() {
     tauri::Builder::default()
         .plugin(tauri_plugin_opener::init())
+        .plugin(tauri_plugin_store::Builder::new().build())
         .manage(AppState::new())
+        .setup(|app| {
+            commands::decoder::load_formatters(app.handle());
+            Ok(())
+        })
         .invoke_handler(tauri::generate_handler![
             commands::connection::connect_redis,
             commands::connection::disconnect_redis,
@@ -19,6 +25,7 @@ fn main() {
             commands::keys::set_key,
             commands::keys::add_set_item,
             commands::keys::get_key_detail,
+            commands::keys::get_key_version,
             commands::keys::delete_set_item,
             commands::keys::delete_key,
             commands::keys::add_zset_item,
@@ -26,9 +33,80 @@ fn main() {
             commands::keys::append_list_item,
             commands::keys::update_hash_field,
             commands::keys::delete_hash_field,
+            commands::keys::get_hash_field_ttls,
+            commands::keys::expire_hash_fields,
+            commands::keys::persist_hash_fields,
             commands::keys::set_key_ttl,
             commands::keys::update_list_item,
             commands::keys::delete_list_item,
+            commands::keys::prepend_list_item,
+            commands::keys::insert_list_item,
+            commands::keys::pop_list_items,
+            commands::keys::trim_list,
+            commands::keys::move_list_item,
+            commands::keys::delete_list_item_by_index,
+            commands::set_ops::set_operation,
+            commands::set_ops::set_operation_store,
+            commands::set_ops::set_intercard,
+            commands::set_ops::set_is_members,
+            commands::set_ops::move_set_item,
+            commands::set_ops::zset_operation,
+            commands::set_ops::zset_operation_store,
+            commands::bitmap::get_bitmap_range,
+            commands::bitmap::get_bit,
+            commands::bitmap::set_bit,
+            commands::bitmap::bit_count,
+            commands::bitmap::bit_pos,
+            commands::bitmap::bit_op,
+            commands::bitmap::bitfield,
+            commands::hyperloglog::get_hyperloglog_info,
+            commands::hyperloglog::pf_add,
+            commands::hyperloglog::pf_count,
+            commands::hyperloglog::pf_merge,
+            commands::geo::get_geo_members,
+            commands::geo::geo_add,
+            commands::geo::geo_dist,
+            commands::geo::geo_search,
+            commands::decoder::list_value_formats,
+            commands::decoder::decode_value,
+            commands::decoder::encode_value,
+            commands::decoder::set_formatters,
+            commands::decoder::get_formatters,
+            commands::protobuf::register_proto_files,
+            commands::protobuf::register_descriptor_set,
+            commands::protobuf::set_proto_mappings,
+            commands::protobuf::get_proto_config,
+            commands::protobuf::clear_proto_registry,
+            commands::strings::get_string_range,
+            commands::strings::set_string_range,
+            commands::strings::append_string,
+            commands::strings::download_string_value,
+            commands::strings::upload_string_value,
+            commands::strings::set_string_value,
+            commands::numeric::incr_by,
+            commands::numeric::decr_by,
+            commands::numeric::incr_by_float,
+            commands::numeric::hincr_by,
+            commands::numeric::hincr_by_float,
+            commands::numeric::zincr_by,
+            commands::transfer::copy_key,
+            commands::transfer::move_key,
+            commands::transfer::transfer_keys,
+            commands::bulk::preview_delete_keys,
+            commands::bulk::start_delete_keys,
+            commands::bulk::preview_ttl_keys,
+            commands::bulk::start_ttl_keys,
+            commands::bulk::cancel_bulk_job,
+            commands::analysis::start_big_key_scan,
+            commands::analysis::start_prefix_analysis,
+            commands::analysis::get_prefix_children,
+            commands::analysis::start_ttl_analysis,
+            commands::analysis::start_hot_key_scan,
+            commands::analysis::get_analysis_report,
+            commands::analysis::export_analysis_report,
+            commands::analysis::delete_analysis_report,
+            commands::keyspace::subscribe_keyspace,
+            commands::keyspace::unsubscribe_keyspace,
             commands::import_export::export_key,
             commands::import_export::export_keys,
             commands::import_export::import_key,
@@ -36,6 +114,7 @@ fn main() {
             commands::database::get_db_count,
             commands::database::get_db_key_count,
             commands::database::get_all_db_key_counts,
+            commands::database::get_keyspace_stats,
             commands::database::select_db,
             commands::server::get_redis_server_info,
         ])
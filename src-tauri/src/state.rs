@@ -1,5 +1,10 @@
+use crate::commands::analysis::SharedReport;
+use crate::commands::keyspace::KeyspaceSubscription;
+use crate::decoder::plugin::FormatterPlugin;
+use crate::decoder::protobuf::ProtoRegistry;
 use redis::Client;
 use std::collections::HashMap;
+use std::sync::atomic::AtomicBool;
 use std::sync::{Arc, Mutex};
 
 // 定义 Redis 连接配置
@@ -18,17 +23,31 @@ pub struct RedisConnectionConfig {
 pub struct ConnectionState {
     pub client: Client,
     pub current_db: u8,
+    pub proto: ProtoRegistry, // Protobuf 描述符和键模式映射
+    pub keyspace: Option<KeyspaceSubscription>, // 键空间通知订阅，移除后调用 shutdown 停止并恢复配置
 }
 
 // 定义应用状态
 pub struct AppState {
     pub connections: Arc<Mutex<HashMap<String, ConnectionState>>>,
+    pub formatters: Arc<Mutex<Vec<FormatterPlugin>>>, // 设置中登记的外部格式化程序
+    pub jobs: Arc<Mutex<HashMap<String, Arc<AtomicBool>>>>, // 运行中的批量任务及其取消标记
+    pub reports: Arc<Mutex<HashMap<String, SharedReport>>>, // 分析任务的报告，按任务 ID 保存
 }
 
 impl AppState {
     pub fn new() -> Self {
         AppState {
             connections: Arc::new(Mutex::new(HashMap::new())),
+            formatters: Arc::new(Mutex::new(Vec::new())),
+            jobs: Arc::new(Mutex::new(HashMap::new())),
+            reports: Arc::new(Mutex::new(HashMap::new())),
         }
     }
 }
+
+impl Default for AppState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
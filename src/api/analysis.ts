@@ -0,0 +1,125 @@
+/**
+ * 键空间分析相关 API
+ */
+import { invoke } from "@tauri-apps/api/core";
+import type {
+	Response,
+	BulkScanOptions,
+	BigKeyOptions,
+	PrefixOptions,
+	PrefixDrillDown,
+	TtlOptions,
+	HotKeyOptions,
+	AnalysisReport,
+} from "./types";
+
+/**
+ * 启动大键扫描任务，返回任务 ID，进度通过 onBulkProgress 监听
+ */
+export async function startBigKeyScan(
+	connectionId: string,
+	options: BulkScanOptions,
+	bigKeyOptions: BigKeyOptions = {},
+): Promise<Response<string>> {
+	return await invoke<Response<string>>("start_big_key_scan", {
+		connectionId,
+		options,
+		bigKeyOptions,
+	});
+}
+
+/**
+ * 启动按前缀汇总内存占用的分析任务，返回任务 ID
+ */
+export async function startPrefixAnalysis(
+	connectionId: string,
+	options: BulkScanOptions,
+	prefixOptions: PrefixOptions = {},
+): Promise<Response<string>> {
+	return await invoke<Response<string>>("start_prefix_analysis", {
+		connectionId,
+		options,
+		prefixOptions,
+	});
+}
+
+/**
+ * 钻取前缀分析报告，prefix 为空时从根节点开始
+ */
+export async function getPrefixChildren(
+	jobId: string,
+	prefix?: string,
+): Promise<Response<PrefixDrillDown>> {
+	return await invoke<Response<PrefixDrillDown>>("get_prefix_children", {
+		jobId,
+		prefix,
+	});
+}
+
+/**
+ * 启动 TTL 分布与过期预测分析任务，返回任务 ID
+ */
+export async function startTtlAnalysis(
+	connectionId: string,
+	options: BulkScanOptions,
+	ttlOptions: TtlOptions = {},
+): Promise<Response<string>> {
+	return await invoke<Response<string>>("start_ttl_analysis", {
+		connectionId,
+		options,
+		ttlOptions,
+	});
+}
+
+/**
+ * 启动热键查找任务：LFU 淘汰策略下扫描 OBJECT FREQ，否则限时运行 MONITOR 采样
+ * MONITOR 模式只统计匹配 options.pattern 的键，不支持 options.key_type
+ */
+export async function startHotKeyScan(
+	connectionId: string,
+	options: BulkScanOptions,
+	hotKeyOptions: HotKeyOptions = {},
+): Promise<Response<string>> {
+	return await invoke<Response<string>>("start_hot_key_scan", {
+		connectionId,
+		options,
+		hotKeyOptions,
+	});
+}
+
+/**
+ * 获取分析报告，任务运行期间返回已处理部分的结果
+ */
+export async function getAnalysisReport(
+	jobId: string,
+): Promise<Response<AnalysisReport>> {
+	return await invoke<Response<AnalysisReport>>("get_analysis_report", {
+		jobId,
+	});
+}
+
+/**
+ * 导出分析报告为 JSON 或 CSV 文件
+ */
+export async function exportAnalysisReport(
+	jobId: string,
+	path: string,
+	format: "json" | "csv",
+): Promise<Response<null>> {
+	return await invoke<Response<null>>("export_analysis_report", {
+		jobId,
+		path,
+		format,
+	});
+}
+
+/**
+ * 删除分析报告，任务仍在运行时一并取消
+ */
+export async function deleteAnalysisReport(
+	jobId: string,
+): Promise<Response<null>> {
+	return await invoke<Response<null>>("delete_analysis_report", {
+		jobId,
+	});
+}
@@ -0,0 +1,130 @@
+/**
+ * 位图相关 API
+ */
+import { invoke } from "@tauri-apps/api/core";
+import type { Response, BitmapRange, BitfieldOperation } from "./types";
+
+export type BitUnit = "byte" | "bit";
+
+/**
+ * 按字节读取位图区间
+ */
+export async function getBitmapRange(
+	connectionId: string,
+	key: string,
+	startByte: number,
+	byteLength: number,
+): Promise<Response<BitmapRange>> {
+	return await invoke<Response<BitmapRange>>("get_bitmap_range", {
+		connectionId,
+		key,
+		startByte,
+		byteLength,
+	});
+}
+
+/**
+ * 获取指定偏移的位
+ */
+export async function getBit(
+	connectionId: string,
+	key: string,
+	offset: number,
+): Promise<Response<number>> {
+	return await invoke<Response<number>>("get_bit", {
+		connectionId,
+		key,
+		offset,
+	});
+}
+
+/**
+ * 设置指定偏移的位，返回原值
+ */
+export async function setBit(
+	connectionId: string,
+	key: string,
+	offset: number,
+	value: 0 | 1,
+): Promise<Response<number>> {
+	return await invoke<Response<number>>("set_bit", {
+		connectionId,
+		key,
+		offset,
+		value,
+	});
+}
+
+/**
+ * 统计值为 1 的位数
+ */
+export async function bitCount(
+	connectionId: string,
+	key: string,
+	start?: number,
+	end?: number,
+	unit?: BitUnit,
+): Promise<Response<number>> {
+	return await invoke<Response<number>>("bit_count", {
+		connectionId,
+		key,
+		start,
+		end,
+		unit,
+	});
+}
+
+/**
+ * 查找第一个指定值的位
+ */
+export async function bitPos(
+	connectionId: string,
+	key: string,
+	bit: 0 | 1,
+	start?: number,
+	end?: number,
+	unit?: BitUnit,
+): Promise<Response<number>> {
+	return await invoke<Response<number>>("bit_pos", {
+		connectionId,
+		key,
+		bit,
+		start,
+		end,
+		unit,
+	});
+}
+
+/**
+ * 位图之间的位运算
+ */
+export async function bitOp(
+	connectionId: string,
+	op: "and" | "or" | "xor" | "not",
+	destination: string,
+	keys: string[],
+): Promise<Response<number>> {
+	return await invoke<Response<number>>("bit_op", {
+		connectionId,
+		op,
+		destination,
+		keys,
+	});
+}
+
+/**
+ * 按类型读写位图中的整数字段
+ */
+export async function bitfield(
+	connectionId: string,
+	key: string,
+	operations: BitfieldOperation[],
+	overflow?: "wrap" | "sat" | "fail",
+): Promise<Response<(number | null)[]>> {
+	return await invoke<Response<(number | null)[]>>("bitfield", {
+		connectionId,
+		key,
+		operations,
+		overflow,
+	});
+}
@@ -0,0 +1,91 @@
+/**
+ * 批量任务相关 API
+ */
+import { invoke } from "@tauri-apps/api/core";
+import { listen, type UnlistenFn } from "@tauri-apps/api/event";
+import type {
+	Response,
+	BulkScanOptions,
+	BulkPreview,
+	BulkProgress,
+	TtlOperation,
+	ExpireCondition,
+} from "./types";
+
+/**
+ * 批量删除试运行，返回匹配的键数和样例
+ */
+export async function previewDeleteKeys(
+	connectionId: string,
+	options: BulkScanOptions,
+): Promise<Response<BulkPreview>> {
+	return await invoke<Response<BulkPreview>>("preview_delete_keys", {
+		connectionId,
+		options,
+	});
+}
+
+/**
+ * 启动批量删除任务，返回任务 ID
+ */
+export async function startDeleteKeys(
+	connectionId: string,
+	options: BulkScanOptions,
+): Promise<Response<string>> {
+	return await invoke<Response<string>>("start_delete_keys", {
+		connectionId,
+		options,
+	});
+}
+
+/**
+ * 批量 TTL 试运行，返回匹配的键数、样例以及会发生变化的键数
+ */
+export async function previewTtlKeys(
+	connectionId: string,
+	options: BulkScanOptions,
+	operation: TtlOperation,
+	condition?: ExpireCondition,
+): Promise<Response<BulkPreview>> {
+	return await invoke<Response<BulkPreview>>("preview_ttl_keys", {
+		connectionId,
+		options,
+		operation,
+		condition,
+	});
+}
+
+/**
+ * 启动批量 TTL 任务，返回任务 ID
+ */
+export async function startTtlKeys(
+	connectionId: string,
+	options: BulkScanOptions,
+	operation: TtlOperation,
+	condition?: ExpireCondition,
+): Promise<Response<string>> {
+	return await invoke<Response<string>>("start_ttl_keys", {
+		connectionId,
+		options,
+		operation,
+		condition,
+	});
+}
+
+/**
+ * 取消批量任务
+ */
+export async function cancelBulkJob(jobId: string): Promise<Response<null>> {
+	return await invoke<Response<null>>("cancel_bulk_job", { jobId });
+}
+
+/**
+ * 监听批量任务进度
+ */
+export async function onBulkProgress(
+	handler: (progress: BulkProgress) => void,
+): Promise<UnlistenFn> {
+	return await listen<BulkProgress>("bulk-job-progress", (event) =>
+		handler(event.payload),
+	);
+}
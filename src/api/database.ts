@@ -39,6 +39,17 @@ export async function getAllDbKeyCounts(
 	});
 }
 
+/**
+ * 获取所有非空数据库的键数、过期键数和平均 TTL
+ */
+export async function getKeyspaceStats(
+	connectionId: string,
+): Promise<Response<DbKeyCount[]>> {
+	return await invoke<Response<DbKeyCount[]>>("get_keyspace_stats", {
+		connectionId,
+	});
+}
+
 /**
  * 切换数据库
  */
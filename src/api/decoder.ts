@@ -0,0 +1,71 @@
+/**
+ * 值解码/编码相关 API
+ */
+import { invoke } from "@tauri-apps/api/core";
+import type {
+	Response,
+	ValueLocation,
+	DecodedValue,
+	FormatterPlugin,
+} from "./types";
+
+/**
+ * 获取支持的值格式列表，指定连接时附带该连接已注册的 Protobuf 消息类型
+ */
+export async function listValueFormats(
+	connectionId?: string,
+): Promise<Response<string[]>> {
+	return await invoke<Response<string[]>>("list_value_formats", {
+		connectionId,
+	});
+}
+
+/**
+ * 解码指定位置的值，不指定格式时自动识别
+ */
+export async function decodeValue(
+	connectionId: string,
+	location: ValueLocation,
+	format?: string,
+): Promise<Response<DecodedValue>> {
+	return await invoke<Response<DecodedValue>>("decode_value", {
+		connectionId,
+		location,
+		format,
+	});
+}
+
+/**
+ * 将编辑后的文本按指定格式编码并写回
+ */
+export async function encodeValue(
+	connectionId: string,
+	location: ValueLocation,
+	format: string,
+	text: string,
+): Promise<Response<null>> {
+	return await invoke<Response<null>>("encode_value", {
+		connectionId,
+		location,
+		format,
+		text,
+	});
+}
+
+/**
+ * 保存设置中登记的格式化程序
+ */
+export async function setFormatters(
+	formatters: FormatterPlugin[],
+): Promise<Response<null>> {
+	return await invoke<Response<null>>("set_formatters", {
+		formatters,
+	});
+}
+
+/**
+ * 获取已登记的格式化程序
+ */
+export async function getFormatters(): Promise<Response<FormatterPlugin[]>> {
+	return await invoke<Response<FormatterPlugin[]>>("get_formatters");
+}
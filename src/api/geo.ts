@@ -0,0 +1,83 @@
+/**
+ * 地理位置相关 API
+ */
+import { invoke } from "@tauri-apps/api/core";
+import type {
+	Response,
+	PageData,
+	GeoMember,
+	GeoPoint,
+	GeoUnit,
+	GeoSearchQuery,
+	GeoSearchItem,
+} from "./types";
+
+/**
+ * 分页获取地理位置成员
+ */
+export async function getGeoMembers(
+	connectionId: string,
+	key: string,
+	offset: number = 0,
+	count: number = 100,
+): Promise<Response<PageData<GeoMember>>> {
+	return await invoke<Response<PageData<GeoMember>>>("get_geo_members", {
+		connectionId,
+		key,
+		offset,
+		count,
+	});
+}
+
+/**
+ * 添加地理位置成员
+ */
+export async function geoAdd(
+	connectionId: string,
+	key: string,
+	points: GeoPoint[],
+): Promise<Response<number>> {
+	return await invoke<Response<number>>("geo_add", {
+		connectionId,
+		key,
+		points,
+	});
+}
+
+/**
+ * 计算两个成员之间的距离
+ */
+export async function geoDist(
+	connectionId: string,
+	key: string,
+	member1: string,
+	member2: string,
+	unit: GeoUnit = "m",
+): Promise<Response<number | null>> {
+	return await invoke<Response<number | null>>("geo_dist", {
+		connectionId,
+		key,
+		member1,
+		member2,
+		unit,
+	});
+}
+
+/**
+ * 按半径或矩形范围搜索成员
+ */
+export async function geoSearch(
+	connectionId: string,
+	key: string,
+	query: GeoSearchQuery,
+	offset: number = 0,
+	count: number = 0,
+): Promise<Response<PageData<GeoSearchItem>>> {
+	return await invoke<Response<PageData<GeoSearchItem>>>("geo_search", {
+		connectionId,
+		key,
+		query,
+		offset,
+		count,
+	});
+}
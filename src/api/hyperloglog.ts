@@ -0,0 +1,61 @@
+/**
+ * HyperLogLog 相关 API
+ */
+import { invoke } from "@tauri-apps/api/core";
+import type { Response, HyperLogLogInfo } from "./types";
+
+/**
+ * 获取 HyperLogLog 信息
+ */
+export async function getHyperLogLogInfo(
+	connectionId: string,
+	key: string,
+): Promise<Response<HyperLogLogInfo>> {
+	return await invoke<Response<HyperLogLogInfo>>("get_hyperloglog_info", {
+		connectionId,
+		key,
+	});
+}
+
+/**
+ * 向 HyperLogLog 添加元素
+ */
+export async function pfAdd(
+	connectionId: string,
+	key: string,
+	elements: string[],
+): Promise<Response<boolean>> {
+	return await invoke<Response<boolean>>("pf_add", {
+		connectionId,
+		key,
+		elements,
+	});
+}
+
+/**
+ * 估算一个或多个 HyperLogLog 并集的基数
+ */
+export async function pfCount(
+	connectionId: string,
+	keys: string[],
+): Promise<Response<number>> {
+	return await invoke<Response<number>>("pf_count", {
+		connectionId,
+		keys,
+	});
+}
+
+/**
+ * 合并多个 HyperLogLog 到目标键
+ */
+export async function pfMerge(
+	connectionId: string,
+	destination: string,
+	sources: string[],
+): Promise<Response<number>> {
+	return await invoke<Response<number>>("pf_merge", {
+		connectionId,
+		destination,
+		sources,
+	});
+}
@@ -8,3 +8,15 @@ export * from "./database";
 export * from "./server";
 export * from "./importExport";
 
+export * from "./setOps";
+export * from "./bitmap";
+export * from "./hyperloglog";
+export * from "./geo";
+export * from "./decoder";
+export * from "./protobuf";
+export * from "./strings";
+export * from "./numeric";
+export * from "./transfer";
+export * from "./bulk";
+export * from "./analysis";
+export * from "./keyspace";
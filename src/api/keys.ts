@@ -6,10 +6,15 @@ import type {
 	Response,
 	KeysListData,
 	KeyDetail,
+	EditResult,
+	KeyTtl,
+	TtlOperation,
+	ExpireCondition,
 } from "./types";
 
 /**
  * 设置键值
+ * mode 为 "nx" 时仅在键不存在时创建，为 "xx" 时仅在键已存在时替换
  */
 export async function setKey(
 	connectionId: string,
@@ -17,13 +22,17 @@ export async function setKey(
 	keyType: string,
 	value: any,
 	ttl: number = 0,
-): Promise<Response<null>> {
-	return await invoke<Response<null>>("set_key", {
+	version?: string,
+	mode?: "nx" | "xx",
+): Promise<Response<EditResult>> {
+	return await invoke<Response<EditResult>>("set_key", {
 		connectionId,
 		key,
 		keyType,
 		value,
 		ttl,
+		version,
+		mode,
 	});
 }
 
@@ -40,6 +49,19 @@ export async function getKey(
 	});
 }
 
+/**
+ * 获取键的版本号，开始编辑时调用，保存时传回用于检测并发修改
+ */
+export async function getKeyVersion(
+	connectionId: string,
+	key: string,
+): Promise<Response<string>> {
+	return await invoke<Response<string>>("get_key_version", {
+		connectionId,
+		key,
+	});
+}
+
 /**
  * 获取键列表
  */
@@ -67,17 +89,19 @@ export async function getKeyDetail(
 }
 
 /**
- * 重命名键
+ * 重命名键，overwrite 为 false 时目标键已存在则不重命名
  */
 export async function renameKey(
 	connectionId: string,
 	oldKey: string,
 	newKey: string,
+	overwrite: boolean = true,
 ): Promise<Response<null>> {
 	return await invoke<Response<null>>("rename_key", {
 		connectionId,
 		oldKey,
 		newKey,
+		overwrite,
 	});
 }
 
@@ -95,17 +119,32 @@ export async function deleteKey(
 }
 
 /**
- * 设置键 TTL
+ * 获取键的过期时间
+ */
+export async function getKeyTtl(
+	connectionId: string,
+	key: string,
+): Promise<Response<KeyTtl>> {
+	return await invoke<Response<KeyTtl>>("get_key_ttl", {
+		connectionId,
+		key,
+	});
+}
+
+/**
+ * 设置键 TTL，condition 对应 NX/XX/GT/LT，成功后返回新的过期时间
  */
 export async function setKeyTtl(
 	connectionId: string,
 	key: string,
-	ttl: number,
-): Promise<Response<null>> {
-	return await invoke<Response<null>>("set_key_ttl", {
+	operation: TtlOperation,
+	condition?: ExpireCondition,
+): Promise<Response<KeyTtl>> {
+	return await invoke<Response<KeyTtl>>("set_key_ttl", {
 		connectionId,
 		key,
-		ttl,
+		operation,
+		condition,
 	});
 }
 
@@ -117,12 +156,14 @@ export async function updateHashField(
 	key: string,
 	field: string,
 	value: string,
-): Promise<Response<null>> {
-	return await invoke<Response<null>>("update_hash_field", {
+	version?: string,
+): Promise<Response<EditResult>> {
+	return await invoke<Response<EditResult>>("update_hash_field", {
 		connectionId,
 		key,
 		field,
 		value,
+		version,
 	});
 }
 
@@ -141,6 +182,57 @@ export async function deleteHashField(
 	});
 }
 
+/**
+ * 获取哈希字段过期时间，返回 [ttl 秒数, 过期毫秒时间戳] 列表
+ */
+export async function getHashFieldTtls(
+	connectionId: string,
+	key: string,
+	fields: string[],
+): Promise<Response<[number, number][]>> {
+	return await invoke<Response<[number, number][]>>("get_hash_field_ttls", {
+		connectionId,
+		key,
+		fields,
+	});
+}
+
+/**
+ * 设置哈希字段过期时间
+ */
+export async function expireHashFields(
+	connectionId: string,
+	key: string,
+	fields: string[],
+	ttl: number,
+	milliseconds: boolean = false,
+	condition?: "nx" | "xx" | "gt" | "lt",
+): Promise<Response<number[]>> {
+	return await invoke<Response<number[]>>("expire_hash_fields", {
+		connectionId,
+		key,
+		fields,
+		ttl,
+		milliseconds,
+		condition,
+	});
+}
+
+/**
+ * 移除哈希字段过期时间
+ */
+export async function persistHashFields(
+	connectionId: string,
+	key: string,
+	fields: string[],
+): Promise<Response<number[]>> {
+	return await invoke<Response<number[]>>("persist_hash_fields", {
+		connectionId,
+		key,
+		fields,
+	});
+}
+
 /**
  * 更新列表项
  */
@@ -149,12 +241,14 @@ export async function updateListItem(
 	key: string,
 	index: number,
 	value: string,
-): Promise<Response<null>> {
-	return await invoke<Response<null>>("update_list_item", {
+	version?: string,
+): Promise<Response<EditResult>> {
+	return await invoke<Response<EditResult>>("update_list_item", {
 		connectionId,
 		key,
 		index,
 		value,
+		version,
 	});
 }
 
@@ -190,6 +284,108 @@ export async function appendListItem(
 	});
 }
 
+/**
+ * 在列表头部添加元素
+ */
+export async function prependListItem(
+	connectionId: string,
+	key: string,
+	value: string,
+): Promise<Response<null>> {
+	return await invoke<Response<null>>("prepend_list_item", {
+		connectionId,
+		key,
+		value,
+	});
+}
+
+/**
+ * 在指定元素前/后插入列表项
+ */
+export async function insertListItem(
+	connectionId: string,
+	key: string,
+	pivot: string,
+	value: string,
+	position: "before" | "after",
+): Promise<Response<null>> {
+	return await invoke<Response<null>>("insert_list_item", {
+		connectionId,
+		key,
+		pivot,
+		value,
+		position,
+	});
+}
+
+/**
+ * 从列表头部或尾部弹出元素
+ */
+export async function popListItems(
+	connectionId: string,
+	key: string,
+	side: "left" | "right",
+	count: number = 1,
+): Promise<Response<string[]>> {
+	return await invoke<Response<string[]>>("pop_list_items", {
+		connectionId,
+		key,
+		side,
+		count,
+	});
+}
+
+/**
+ * 裁剪列表
+ */
+export async function trimList(
+	connectionId: string,
+	key: string,
+	start: number,
+	stop: number,
+): Promise<Response<null>> {
+	return await invoke<Response<null>>("trim_list", {
+		connectionId,
+		key,
+		start,
+		stop,
+	});
+}
+
+/**
+ * 在两个列表之间移动元素
+ */
+export async function moveListItem(
+	connectionId: string,
+	source: string,
+	destination: string,
+	fromSide: "left" | "right",
+	toSide: "left" | "right",
+): Promise<Response<string>> {
+	return await invoke<Response<string>>("move_list_item", {
+		connectionId,
+		source,
+		destination,
+		fromSide,
+		toSide,
+	});
+}
+
+/**
+ * 按索引删除列表项
+ */
+export async function deleteListItemByIndex(
+	connectionId: string,
+	key: string,
+	index: number,
+): Promise<Response<null>> {
+	return await invoke<Response<null>>("delete_list_item_by_index", {
+		connectionId,
+		key,
+		index,
+	});
+}
+
 /**
  * 添加 Set 元素
  */
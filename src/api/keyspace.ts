@@ -0,0 +1,60 @@
+/**
+ * 键空间通知相关 API
+ */
+import { invoke } from "@tauri-apps/api/core";
+import { listen, type UnlistenFn } from "@tauri-apps/api/event";
+import type {
+	Response,
+	KeyspaceSubscriptionInfo,
+	KeyspaceEvent,
+	KeyspaceClosed,
+} from "./types";
+
+/**
+ * 订阅键空间通知，keys 和 pattern 都为空时订阅当前库的全部键
+ * enableNotifications 为 true 时，服务端未开启键空间通知则临时开启（全局生效），取消订阅或断开连接后恢复
+ */
+export async function subscribeKeyspace(
+	connectionId: string,
+	keys?: string[],
+	pattern?: string,
+	enableNotifications = false,
+): Promise<Response<KeyspaceSubscriptionInfo>> {
+	return await invoke<Response<KeyspaceSubscriptionInfo>>(
+		"subscribe_keyspace",
+		{ connectionId, keys, pattern, enableNotifications },
+	);
+}
+
+/**
+ * 取消键空间通知订阅
+ */
+export async function unsubscribeKeyspace(
+	connectionId: string,
+): Promise<Response<null>> {
+	return await invoke<Response<null>>("unsubscribe_keyspace", {
+		connectionId,
+	});
+}
+
+/**
+ * 监听键变化事件
+ */
+export async function onKeyspaceEvent(
+	handler: (event: KeyspaceEvent) => void,
+): Promise<UnlistenFn> {
+	return await listen<KeyspaceEvent>("keyspace-event", (event) =>
+		handler(event.payload),
+	);
+}
+
+/**
+ * 监听订阅因错误中断的事件，中断后需要重新订阅
+ */
+export async function onKeyspaceClosed(
+	handler: (event: KeyspaceClosed) => void,
+): Promise<UnlistenFn> {
+	return await listen<KeyspaceClosed>("keyspace-closed", (event) =>
+		handler(event.payload),
+	);
+}
@@ -0,0 +1,101 @@
+/**
+ * 数值自增/自减相关 API
+ */
+import { invoke } from "@tauri-apps/api/core";
+import type { Response } from "./types";
+
+/**
+ * 整数自增
+ */
+export async function incrBy(
+	connectionId: string,
+	key: string,
+	increment: number,
+): Promise<Response<number>> {
+	return await invoke<Response<number>>("incr_by", {
+		connectionId,
+		key,
+		increment,
+	});
+}
+
+/**
+ * 整数自减
+ */
+export async function decrBy(
+	connectionId: string,
+	key: string,
+	decrement: number,
+): Promise<Response<number>> {
+	return await invoke<Response<number>>("decr_by", {
+		connectionId,
+		key,
+		decrement,
+	});
+}
+
+/**
+ * 浮点数自增，结果以字符串返回以保留精度
+ */
+export async function incrByFloat(
+	connectionId: string,
+	key: string,
+	increment: number,
+): Promise<Response<string>> {
+	return await invoke<Response<string>>("incr_by_float", {
+		connectionId,
+		key,
+		increment,
+	});
+}
+
+/**
+ * 哈希字段整数自增
+ */
+export async function hincrBy(
+	connectionId: string,
+	key: string,
+	field: string,
+	increment: number,
+): Promise<Response<number>> {
+	return await invoke<Response<number>>("hincr_by", {
+		connectionId,
+		key,
+		field,
+		increment,
+	});
+}
+
+/**
+ * 哈希字段浮点数自增
+ */
+export async function hincrByFloat(
+	connectionId: string,
+	key: string,
+	field: string,
+	increment: number,
+): Promise<Response<string>> {
+	return await invoke<Response<string>>("hincr_by_float", {
+		connectionId,
+		key,
+		field,
+		increment,
+	});
+}
+
+/**
+ * 有序集合成员分数自增
+ */
+export async function zincrBy(
+	connectionId: string,
+	key: string,
+	member: string,
+	increment: number,
+): Promise<Response<number>> {
+	return await invoke<Response<number>>("zincr_by", {
+		connectionId,
+		key,
+		member,
+		increment,
+	});
+}
@@ -0,0 +1,68 @@
+/**
+ * Protobuf 描述符与映射相关 API
+ */
+import { invoke } from "@tauri-apps/api/core";
+import type { Response, ProtoMapping, ProtoConfig } from "./types";
+
+/**
+ * 注册 .proto 文件，返回已注册的全部消息类型
+ */
+export async function registerProtoFiles(
+	connectionId: string,
+	files: string[],
+	includePaths: string[] = [],
+): Promise<Response<string[]>> {
+	return await invoke<Response<string[]>>("register_proto_files", {
+		connectionId,
+		files,
+		includePaths,
+	});
+}
+
+/**
+ * 注册编译好的 FileDescriptorSet 文件，返回已注册的全部消息类型
+ */
+export async function registerDescriptorSet(
+	connectionId: string,
+	path: string,
+): Promise<Response<string[]>> {
+	return await invoke<Response<string[]>>("register_descriptor_set", {
+		connectionId,
+		path,
+	});
+}
+
+/**
+ * 设置键模式到消息类型的映射
+ */
+export async function setProtoMappings(
+	connectionId: string,
+	mappings: ProtoMapping[],
+): Promise<Response<null>> {
+	return await invoke<Response<null>>("set_proto_mappings", {
+		connectionId,
+		mappings,
+	});
+}
+
+/**
+ * 获取已注册的消息类型和映射
+ */
+export async function getProtoConfig(
+	connectionId: string,
+): Promise<Response<ProtoConfig>> {
+	return await invoke<Response<ProtoConfig>>("get_proto_config", {
+		connectionId,
+	});
+}
+
+/**
+ * 清空 Protobuf 配置
+ */
+export async function clearProtoRegistry(
+	connectionId: string,
+): Promise<Response<null>> {
+	return await invoke<Response<null>>("clear_proto_registry", {
+		connectionId,
+	});
+}
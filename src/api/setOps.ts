@@ -0,0 +1,135 @@
+/**
+ * 集合运算相关 API
+ */
+import { invoke } from "@tauri-apps/api/core";
+import type { Response, PageData, ZSetItem } from "./types";
+
+export type SetOperation = "inter" | "union" | "diff";
+export type ZSetAggregate = "sum" | "min" | "max";
+
+/**
+ * 集合运算（交集/并集/差集）
+ */
+export async function setOperation(
+	connectionId: string,
+	op: SetOperation,
+	keys: string[],
+	offset: number = 0,
+	count: number = 0,
+): Promise<Response<PageData<string>>> {
+	return await invoke<Response<PageData<string>>>("set_operation", {
+		connectionId,
+		op,
+		keys,
+		offset,
+		count,
+	});
+}
+
+/**
+ * 集合运算并保存到目标键
+ */
+export async function setOperationStore(
+	connectionId: string,
+	op: SetOperation,
+	destination: string,
+	keys: string[],
+): Promise<Response<number>> {
+	return await invoke<Response<number>>("set_operation_store", {
+		connectionId,
+		op,
+		destination,
+		keys,
+	});
+}
+
+/**
+ * 计算交集基数
+ */
+export async function setIntercard(
+	connectionId: string,
+	keys: string[],
+	limit: number = 0,
+): Promise<Response<number>> {
+	return await invoke<Response<number>>("set_intercard", {
+		connectionId,
+		keys,
+		limit,
+	});
+}
+
+/**
+ * 批量判断成员是否属于集合
+ */
+export async function setIsMembers(
+	connectionId: string,
+	key: string,
+	members: string[],
+): Promise<Response<boolean[]>> {
+	return await invoke<Response<boolean[]>>("set_is_members", {
+		connectionId,
+		key,
+		members,
+	});
+}
+
+/**
+ * 在集合之间移动成员
+ */
+export async function moveSetItem(
+	connectionId: string,
+	source: string,
+	destination: string,
+	member: string,
+): Promise<Response<null>> {
+	return await invoke<Response<null>>("move_set_item", {
+		connectionId,
+		source,
+		destination,
+		member,
+	});
+}
+
+/**
+ * 有序集合运算（交集/并集/差集）
+ */
+export async function zsetOperation(
+	connectionId: string,
+	op: SetOperation,
+	keys: string[],
+	weights?: number[],
+	aggregate?: ZSetAggregate,
+	offset: number = 0,
+	count: number = 0,
+): Promise<Response<PageData<ZSetItem>>> {
+	return await invoke<Response<PageData<ZSetItem>>>("zset_operation", {
+		connectionId,
+		op,
+		keys,
+		weights,
+		aggregate,
+		offset,
+		count,
+	});
+}
+
+/**
+ * 有序集合运算并保存到目标键
+ */
+export async function zsetOperationStore(
+	connectionId: string,
+	op: SetOperation,
+	destination: string,
+	keys: string[],
+	weights?: number[],
+	aggregate?: ZSetAggregate,
+): Promise<Response<number>> {
+	return await invoke<Response<number>>("zset_operation_store", {
+		connectionId,
+		op,
+		destination,
+		keys,
+		weights,
+		aggregate,
+	});
+}
@@ -0,0 +1,107 @@
+/**
+ * 大字符串分段读写相关 API
+ */
+import { invoke } from "@tauri-apps/api/core";
+import type { Response, StringRange, StringEncoding } from "./types";
+
+/**
+ * 读取字符串的字节区间
+ */
+export async function getStringRange(
+	connectionId: string,
+	key: string,
+	offset: number,
+	length: number,
+	encoding: StringEncoding = "text",
+): Promise<Response<StringRange>> {
+	return await invoke<Response<StringRange>>("get_string_range", {
+		connectionId,
+		key,
+		offset,
+		length,
+		encoding,
+	});
+}
+
+/**
+ * 从指定偏移覆盖写入，返回写入后的长度
+ */
+export async function setStringRange(
+	connectionId: string,
+	key: string,
+	offset: number,
+	data: string,
+	encoding: StringEncoding = "text",
+): Promise<Response<number>> {
+	return await invoke<Response<number>>("set_string_range", {
+		connectionId,
+		key,
+		offset,
+		data,
+		encoding,
+	});
+}
+
+/**
+ * 在字符串末尾追加内容，返回追加后的长度
+ */
+export async function appendString(
+	connectionId: string,
+	key: string,
+	data: string,
+	encoding: StringEncoding = "text",
+): Promise<Response<number>> {
+	return await invoke<Response<number>>("append_string", {
+		connectionId,
+		key,
+		data,
+		encoding,
+	});
+}
+
+/**
+ * 下载字符串值到本地文件
+ */
+export async function downloadStringValue(
+	connectionId: string,
+	key: string,
+	path: string,
+): Promise<Response<number>> {
+	return await invoke<Response<number>>("download_string_value", {
+		connectionId,
+		key,
+		path,
+	});
+}
+
+/**
+ * 从本地文件上传字符串值
+ */
+export async function uploadStringValue(
+	connectionId: string,
+	key: string,
+	path: string,
+): Promise<Response<number>> {
+	return await invoke<Response<number>>("upload_string_value", {
+		connectionId,
+		key,
+		path,
+	});
+}
+
+/**
+ * 更新字符串值并保留 TTL，returnOld 为 true 时返回旧值
+ */
+export async function setStringValue(
+	connectionId: string,
+	key: string,
+	value: string,
+	returnOld: boolean = false,
+): Promise<Response<string | null>> {
+	return await invoke<Response<string | null>>("set_string_value", {
+		connectionId,
+		key,
+		value,
+		returnOld,
+	});
+}
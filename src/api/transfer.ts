@@ -0,0 +1,61 @@
+/**
+ * 键复制/移动/跨连接迁移相关 API
+ */
+import { invoke } from "@tauri-apps/api/core";
+import type { Response, TransferResult } from "./types";
+
+/**
+ * 复制键，targetDb 为空时复制到当前库
+ */
+export async function copyKey(
+	connectionId: string,
+	sourceKey: string,
+	targetKey: string,
+	targetDb?: number,
+	replace: boolean = false,
+): Promise<Response<null>> {
+	return await invoke<Response<null>>("copy_key", {
+		connectionId,
+		sourceKey,
+		targetKey,
+		targetDb,
+		replace,
+	});
+}
+
+/**
+ * 移动键到其他库
+ */
+export async function moveKey(
+	connectionId: string,
+	key: string,
+	targetDb: number,
+): Promise<Response<null>> {
+	return await invoke<Response<null>>("move_key", {
+		connectionId,
+		key,
+		targetDb,
+	});
+}
+
+/**
+ * 将匹配模式的键迁移到另一个连接（保留 TTL），targetDb 为空时使用目标连接的当前库
+ * exactKey 为 true 时 pattern 为完整键名，只迁移该键
+ */
+export async function transferKeys(
+	sourceConnectionId: string,
+	targetConnectionId: string,
+	pattern: string,
+	targetDb?: number,
+	replace: boolean = false,
+	exactKey: boolean = false,
+): Promise<Response<TransferResult>> {
+	return await invoke<Response<TransferResult>>("transfer_keys", {
+		sourceConnectionId,
+		targetConnectionId,
+		pattern,
+		targetDb,
+		replace,
+		exactKey,
+	});
+}
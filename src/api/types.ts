@@ -44,17 +44,60 @@ export interface KeyDetail {
 	key: string;
 	type: string;
 	ttl: number;
+	/** 剩余过期毫秒数，-1 表示永久 */
+	pttl: number;
+	/** 绝对过期毫秒时间戳，-1 表示永久 */
+	expire_at: number;
+	/** MEMORY USAGE ... SAMPLES 0 的结果（字节） */
 	size: number;
-	create_time: string;
+	/** OBJECT ENCODING，例如 listpack、hashtable */
+	encoding?: string;
+	/** OBJECT IDLETIME（秒），LFU 淘汰策略下为空 */
+	idle_time?: number;
+	/** OBJECT FREQ，只在 LFU 淘汰策略下可用 */
+	lfu_freq?: number;
+	/** OBJECT REFCOUNT */
+	refcount?: number;
+	/** 元素个数，字符串为字节长度 */
+	length?: number;
+	/** 值的解码格式，例如 "protobuf:shop.v1.Order" */
+	format?: string;
+	/** 字符串值只返回了预览时为完整长度 */
+	total_length?: number;
 	value: any;
 }
 
+/**
+ * 键的过期时间，-1 表示永久，-2 表示键不存在
+ */
+export interface KeyTtl {
+	ttl: number;
+	pttl: number;
+	/** 绝对过期毫秒时间戳 */
+	expire_at: number;
+}
+
+/**
+ * 编辑结果
+ */
+export interface EditResult {
+	conflict: boolean;
+	/** 写入后的版本号；冲突时为服务器上的当前版本号 */
+	version?: string;
+	/** 冲突时服务器上的当前值 */
+	current_value?: any;
+}
+
 /**
  * 数据库键数量
  */
 export interface DbKeyCount {
 	db_index: number;
 	key_count: number;
+	/** 设置了过期时间的键数 */
+	expires: number;
+	/** 带过期时间的键的平均剩余 TTL（毫秒） */
+	avg_ttl: number;
 }
 
 /**
@@ -82,3 +125,467 @@ export interface RedisServerInfo {
 	connected_slaves: number;
 }
 
+
+/**
+ * 分页数据
+ */
+export interface PageData<T> {
+	items: T[];
+	total: number;
+	offset: number;
+	count: number;
+}
+
+/**
+ * 有序集合成员
+ */
+export interface ZSetItem {
+	value: string;
+	score: number;
+}
+
+/**
+ * 位图区间数据
+ */
+export interface BitmapRange {
+	start_byte: number;
+	byte_length: number;
+	total_bytes: number;
+	bits: string;
+}
+
+/**
+ * BITFIELD 子操作
+ */
+export interface BitfieldOperation {
+	op: "get" | "set" | "incrby";
+	encoding: string;
+	offset: string;
+	value?: number;
+}
+
+/**
+ * HyperLogLog 信息（键详情类型为 hyperloglog 时的 value）
+ */
+export interface HyperLogLogInfo {
+	count: number;
+	encoding: "dense" | "sparse";
+}
+
+/**
+ * 地理位置成员
+ */
+export interface GeoMember {
+	member: string;
+	longitude: number | null;
+	latitude: number | null;
+	geohash: string | null;
+}
+
+/**
+ * 待添加的地理位置
+ */
+export interface GeoPoint {
+	member: string;
+	longitude: number;
+	latitude: number;
+}
+
+export type GeoUnit = "m" | "km" | "ft" | "mi";
+
+/**
+ * 地理搜索条件
+ */
+export interface GeoSearchQuery {
+	from_member?: string;
+	from_longitude?: number;
+	from_latitude?: number;
+	radius?: number;
+	width?: number;
+	height?: number;
+	unit: GeoUnit;
+	order?: "asc" | "desc";
+}
+
+/**
+ * 地理搜索结果项
+ */
+export interface GeoSearchItem {
+	member: string;
+	distance: number;
+	longitude: number;
+	latitude: number;
+}
+
+/**
+ * 值所在位置：字符串键本身、哈希字段或列表元素
+ */
+export interface ValueLocation {
+	key: string;
+	field?: string;
+	index?: number;
+}
+
+/**
+ * 值解码结果
+ */
+export interface DecodedValue {
+	format: string;
+	detected: boolean;
+	text: string;
+}
+
+/**
+ * 键模式到 Protobuf 消息类型的映射
+ */
+export interface ProtoMapping {
+	pattern: string;
+	message_type: string;
+}
+
+/**
+ * 连接的 Protobuf 配置
+ */
+export interface ProtoConfig {
+	message_types: string[];
+	mappings: ProtoMapping[];
+}
+
+/**
+ * 外部格式化程序
+ */
+export interface FormatterPlugin {
+	name: string;
+	decode_command: string;
+	decode_args?: string[];
+	/** 为空时该格式只读 */
+	encode_command?: string | null;
+	encode_args?: string[];
+	/** 自动识别时按键名匹配的模式 */
+	patterns?: string[];
+	timeout_ms?: number;
+}
+
+/**
+ * 字符串区间数据
+ */
+export interface StringRange {
+	offset: number;
+	length: number;
+	total_length: number;
+	data: string;
+}
+
+/**
+ * 字符串区间内容的编码方式
+ */
+export type StringEncoding = "text" | "hex" | "base64";
+
+/**
+ * 跨连接迁移结果
+ */
+export interface TransferResult {
+	transferred: number;
+	/** 目标已存在且未指定覆盖的键 */
+	skipped: string[];
+	/** 迁移失败的键及原因 */
+	failed: string[];
+}
+
+/**
+ * 批量任务的扫描条件
+ */
+export interface BulkScanOptions {
+	pattern: string;
+	/** 只处理指定类型的键，例如 "hash" */
+	key_type?: string;
+	/** 每批 SCAN / 处理的键数，默认 500 */
+	batch_size?: number;
+	/** 每批处理后暂停的毫秒数 */
+	throttle_ms?: number;
+}
+
+/**
+ * 批量任务试运行结果
+ */
+export interface BulkPreview {
+	total: number;
+	sample: string[];
+	/** 执行后实际会发生变化的键数，只有带条件的任务会返回 */
+	affected?: number;
+}
+
+/**
+ * 批量任务进度
+ */
+export interface BulkProgress {
+	job_id: string;
+	scanned: number;
+	matched: number;
+	processed: number;
+	done: boolean;
+	cancelled: boolean;
+	error?: string;
+}
+
+/**
+ * TTL 操作：相对毫秒数、绝对毫秒时间戳或移除过期时间
+ */
+export type TtlOperation =
+	| { action: "expire"; ttl_ms: number }
+	| { action: "expire_at"; timestamp_ms: number }
+	| { action: "persist" };
+
+/**
+ * EXPIRE 系列命令的生效条件（Redis 7.0+）
+ */
+export type ExpireCondition = "nx" | "xx" | "gt" | "lt";
+
+/**
+ * 大键扫描参数
+ */
+export interface BigKeyOptions {
+	/** 每种类型保留的大键数量，默认 10 */
+	top_n?: number;
+	/** 内存占用达到该字节数视为大键 */
+	min_bytes?: number;
+	/** 元素个数达到该值视为大键，与 min_bytes 满足其一即可 */
+	min_length?: number;
+	rank_by?: "bytes" | "length";
+	/** MEMORY USAGE 的 SAMPLES 参数，默认 5，0 表示统计全部元素 */
+	samples?: number;
+}
+
+/**
+ * 单个键的大小
+ */
+export interface BigKeyEntry {
+	key: string;
+	type: string;
+	bytes?: number;
+	length?: number;
+}
+
+/**
+ * 按类型汇总的大键统计
+ */
+export interface BigKeyTypeStats {
+	keys: number;
+	total_bytes: number;
+	total_length: number;
+	big_keys: number;
+	top: BigKeyEntry[];
+}
+
+/**
+ * 大键扫描报告
+ */
+export interface BigKeyReport {
+	kind: "big_keys";
+	options: Required<Omit<BigKeyOptions, "min_bytes" | "min_length">> &
+		Pick<BigKeyOptions, "min_bytes" | "min_length">;
+	analyzed: number;
+	types: Record<string, BigKeyTypeStats>;
+}
+
+/**
+ * 前缀分析参数
+ */
+export interface PrefixOptions {
+	/** 键名分隔符，默认 ":" */
+	separator?: string;
+	/** 最多统计的前缀层数，默认 3 */
+	depth?: number;
+	/** 抽样比例 (0, 1]，默认 1 */
+	sample_rate?: number;
+	/** MEMORY USAGE 的 SAMPLES 参数，默认 5 */
+	samples?: number;
+	/** 最多统计的前缀数，超出后新出现的前缀计入 other，默认 10000 */
+	max_prefixes?: number;
+}
+
+/**
+ * 单个前缀的统计（基于抽样的键）
+ */
+export interface PrefixStats {
+	prefix: string;
+	/** 前缀层级，根节点为 0 */
+	level: number;
+	keys: number;
+	total_bytes: number;
+	avg_bytes: number;
+	/** 按抽样比例估算的键数 */
+	estimated_keys: number;
+	estimated_bytes: number;
+	types: Record<string, number>;
+	/** 没有过期时间的键数 */
+	no_ttl: number;
+	no_ttl_percent: number;
+}
+
+/**
+ * 前缀内存分析报告
+ */
+export interface PrefixReport {
+	kind: "prefixes";
+	options: Required<PrefixOptions>;
+	root: PrefixStats;
+	prefixes: Record<string, PrefixStats>;
+	/** 前缀数达到 max_prefixes 后无法单独统计的键 */
+	other: PrefixStats;
+}
+
+/**
+ * 前缀钻取结果
+ */
+export interface PrefixDrillDown {
+	stats: PrefixStats;
+	children: PrefixStats[];
+}
+
+/**
+ * TTL 分析参数
+ */
+export interface TtlOptions {
+	/** 键名分隔符，默认 ":" */
+	separator?: string;
+	/** 按前缀拆分的层数，默认 1，0 表示只统计整体 */
+	depth?: number;
+	/** 抽样比例 (0, 1]，默认 1 */
+	sample_rate?: number;
+	/** MEMORY USAGE 的 SAMPLES 参数，默认 5 */
+	samples?: number;
+	/** 预测的时间窗口（毫秒），默认 1 分钟、1 小时、1 天、7 天 */
+	windows_ms?: number[];
+	/** 最多统计的前缀数，超出后新出现的前缀计入 other，默认 10000 */
+	max_prefixes?: number;
+}
+
+/**
+ * TTL 直方图区间
+ */
+export interface TtlBucket {
+	keys: number;
+	bytes: number;
+}
+
+/**
+ * 剩余过期时间直方图
+ */
+export interface TtlHistogram {
+	no_ttl: TtlBucket;
+	lt_1m: TtlBucket;
+	lt_1h: TtlBucket;
+	lt_1d: TtlBucket;
+	gt_1d: TtlBucket;
+}
+
+/**
+ * 某个时间窗口内将过期的键
+ */
+export interface ExpiryForecast {
+	within_ms: number;
+	keys: number;
+	bytes: number;
+	estimated_keys: number;
+	estimated_bytes: number;
+}
+
+/**
+ * 单个前缀的 TTL 统计
+ */
+export interface TtlStats {
+	prefix: string;
+	level: number;
+	keys: number;
+	histogram: TtlHistogram;
+	forecast: ExpiryForecast[];
+}
+
+/**
+ * TTL 分布与过期预测报告
+ */
+export interface TtlReport {
+	kind: "ttl";
+	options: Required<TtlOptions>;
+	/** 任务开始的毫秒时间戳 */
+	started_at: number;
+	root: TtlStats;
+	prefixes: Record<string, TtlStats>;
+	/** 前缀数达到 max_prefixes 后无法单独统计的键 */
+	other: TtlStats;
+}
+
+/**
+ * 热键查找参数
+ */
+export interface HotKeyOptions {
+	/** 保留的热键数量，默认 20 */
+	top_n?: number;
+	/** MONITOR 采样时长（秒），默认 10 */
+	monitor_seconds?: number;
+	/** LFU 策略下也使用 MONITOR 采样 */
+	force_monitor?: boolean;
+}
+
+/**
+ * 热键
+ */
+export interface HotKeyEntry {
+	key: string;
+	/** LFU 模式下为 OBJECT FREQ，MONITOR 模式下为采样期间的访问次数 */
+	count: number;
+	/** MONITOR 模式下各命令的访问次数 */
+	commands: Record<string, number>;
+}
+
+/**
+ * 热键报告
+ */
+export interface HotKeyReport {
+	kind: "hot_keys";
+	mode: "lfu" | "monitor";
+	options: Required<HotKeyOptions>;
+	observed: number;
+	keys: HotKeyEntry[];
+}
+
+/**
+ * 分析报告
+ */
+export type AnalysisReport =
+	| BigKeyReport
+	| PrefixReport
+	| TtlReport
+	| HotKeyReport;
+
+/**
+ * 键空间通知订阅结果
+ */
+export interface KeyspaceSubscriptionInfo {
+	db: number;
+	patterns: string[];
+	/** 服务端的 notify-keyspace-events 配置，无法读取时为空 */
+	notify_keyspace_events: string;
+	/** 本次订阅是否临时开启了键空间通知，取消订阅或断开连接时恢复 */
+	enabled_notifications: boolean;
+}
+
+/**
+ * 键变化事件
+ */
+export interface KeyspaceEvent {
+	connection_id: string;
+	db: number;
+	key: string;
+	/** 例如 set、del、expire、expired、evicted、hset */
+	event: string;
+}
+
+/**
+ * 键空间通知订阅因错误中断
+ */
+export interface KeyspaceClosed {
+	connection_id: string;
+	error: string;
+}
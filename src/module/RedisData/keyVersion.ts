@@ -0,0 +1,54 @@
+/**
+ * 编辑时的并发修改检测
+ * 开始编辑时按需获取版本号，保存时传回；版本不一致说明键已被其他客户端修改
+ */
+import { message } from "ant-design-vue";
+import { getKeyVersion } from "@/api";
+import type { EditResult, Response } from "@/api";
+import { useConnectionStore } from "@/stores/useConnectionStore.ts";
+
+interface VersionedKey {
+	key: string;
+	/** 开始编辑时获取的版本号，键被本地的其他操作修改后需要清除 */
+	version?: string;
+}
+
+/**
+ * 开始编辑前获取版本号，已获取过时直接返回
+ */
+export const ensureKeyVersion = async (
+	keyData: VersionedKey,
+): Promise<string | undefined> => {
+	const connectionStore = useConnectionStore();
+	if (keyData.version !== undefined || !connectionStore.activeConnection?.id) {
+		return keyData.version;
+	}
+
+	const res = await getKeyVersion(connectionStore.activeConnection.id, keyData.key);
+	if (res.success && res.data !== undefined) {
+		keyData.version = res.data;
+	}
+	return keyData.version;
+};
+
+/**
+ * 处理带版本号的写入结果：成功时记录新版本号，冲突时提示并重新加载键详情
+ * 返回写入是否成功
+ */
+export const applyEditResult = (
+	keyData: VersionedKey,
+	res: Response<EditResult>,
+): boolean => {
+	if (res.success) {
+		keyData.version = res.data?.version;
+		return true;
+	}
+
+	if (res.data?.conflict) {
+		const connectionStore = useConnectionStore();
+		keyData.version = undefined;
+		message.error(res.message || "键已被其他客户端修改，已重新加载");
+		connectionStore.setCurrentKey(keyData.key);
+	}
+	return false;
+};
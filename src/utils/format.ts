@@ -29,6 +29,7 @@ export const getTypeColor = (type: string) => {
     list: "orange",
     set: "purple",
     zset: "red",
+    hyperloglog: "cyan",
   };
   return colors[type] || "gray";
 };